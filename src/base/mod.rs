@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests;
 
+use crate::eval::{self, EvalContext, EvalOptions, Expression, ThisContext, Val};
+use crate::note::{NoteContext, Task};
+use serde_json::json;
 use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
@@ -10,6 +14,51 @@ use std::path::Path;
 pub struct BaseSpec {
     pub filters: Option<FilterNode>,
     pub views: Vec<ViewSpec>,
+    /// Column display names from `properties.<id>.displayName`, keyed as written in the file.
+    pub display_names: HashMap<String, String>,
+}
+
+impl BaseSpec {
+    /// Build an in-memory base with a single `Query` table view.
+    ///
+    /// All filter expressions must match (they form one `and` list).
+    pub fn query(filters: Vec<String>, sort: Vec<SortSpec>, order: Vec<String>) -> Self {
+        let filters = if filters.is_empty() {
+            None
+        } else {
+            Some(FilterNode::And(
+                filters.into_iter().map(FilterEntry::Expr).collect(),
+            ))
+        };
+        Self {
+            filters: None,
+            views: vec![ViewSpec {
+                view_type: "table".to_owned(),
+                name: "Query".to_owned(),
+                filters,
+                order,
+                sort,
+            }],
+            display_names: HashMap::new(),
+        }
+    }
+
+    /// Header for a column: its configured display name, or the column key without namespace.
+    ///
+    /// `rating`, `note.rating` and `property.rating` all refer to the same property.
+    pub fn column_header(&self, col: &str) -> String {
+        let key = column_key(col);
+        [
+            col.to_owned(),
+            format!("note.{key}"),
+            format!("property.{key}"),
+            key.to_owned(),
+        ]
+        .iter()
+        .find_map(|id| self.display_names.get(id))
+        .cloned()
+        .unwrap_or_else(|| key.to_owned())
+    }
 }
 
 /// A single view within the base (table, cards, list).
@@ -29,6 +78,21 @@ pub struct SortSpec {
     pub direction: SortDirection,
 }
 
+impl SortSpec {
+    /// Parse a `property[:asc|:desc]` shorthand, e.g. `finished:desc`.
+    pub fn parse(s: &str) -> Self {
+        let (property, direction) = match s.rsplit_once(':') {
+            Some((p, d)) if d.eq_ignore_ascii_case("desc") => (p, SortDirection::Desc),
+            Some((p, d)) if d.eq_ignore_ascii_case("asc") => (p, SortDirection::Asc),
+            _ => (s, SortDirection::Asc),
+        };
+        Self {
+            property: property.to_owned(),
+            direction,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortDirection {
     Asc,
@@ -67,9 +131,21 @@ pub fn parse_str(content: &str) -> Result<BaseSpec, String> {
         _ => Vec::new(),
     };
 
+    let display_names = match value.get("properties") {
+        Some(Value::Mapping(map)) => map
+            .iter()
+            .filter_map(|(k, v)| {
+                let name = v.get("displayName").and_then(Value::as_str)?;
+                Some((k.as_str()?.to_owned(), name.to_owned()))
+            })
+            .collect(),
+        _ => HashMap::new(),
+    };
+
     Ok(BaseSpec {
         filters: top_filters,
         views,
+        display_names,
     })
 }
 
@@ -149,3 +225,567 @@ fn parse_sort(val: &Value) -> Option<SortSpec> {
         direction,
     })
 }
+
+// ─── Resolution ─────────────────────────────────────────────
+
+/// A base evaluated against a set of notes: one entry per view.
+#[derive(Debug, Clone)]
+pub struct ResolvedBase {
+    pub views: Vec<ResolvedView>,
+}
+
+/// Matched, sorted rows of a single view.
+#[derive(Debug, Clone)]
+pub struct ResolvedView {
+    pub name: String,
+    pub view_type: String,
+    /// Column identifiers from the view's `order` (e.g. `file.name`, `property.status`).
+    pub columns: Vec<String>,
+    /// Human-readable header per column (display name when configured).
+    pub headers: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+/// A matched note with display values for each view column.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub file: String,
+    pub name: String,
+    /// One value per entry in `ResolvedView::columns`; `Null` when the note lacks the property.
+    pub cells: Vec<serde_json::Value>,
+}
+
+impl ResolvedView {
+    /// Render a row as a JSON object: `view`, `file`, `name`, then one key per column.
+    ///
+    /// Column keys drop their `file.`/`property.`/`note.` prefix; null cells are omitted.
+    pub fn row_json(&self, row: &Row) -> serde_json::Map<String, serde_json::Value> {
+        let mut obj = serde_json::Map::new();
+        obj.insert("view".into(), json!(self.name));
+        obj.insert("file".into(), json!(row.file));
+        obj.insert("name".into(), json!(row.name));
+
+        for (col, val) in self.columns.iter().zip(&row.cells) {
+            let key = column_key(col);
+            if key == "name" || obj.contains_key(key) || val.is_null() {
+                continue;
+            }
+            obj.insert(key.to_owned(), val.clone());
+        }
+        obj
+    }
+}
+
+/// Resolve every view of a base against the given notes, with default evaluation options.
+///
+/// Top-level filters apply to all views; each view then applies its own filters and sorts.
+pub fn resolve(spec: &BaseSpec, notes: &[NoteContext], this_ctx: &ThisContext) -> ResolvedBase {
+    resolve_with(spec, &EvalContext::new(this_ctx, notes))
+}
+
+/// Resolve every view of a base against the notes of `ctx`, honoring its options.
+pub fn resolve_with(spec: &BaseSpec, ctx: &EvalContext) -> ResolvedBase {
+    let candidates: Vec<&NoteContext> = ctx
+        .notes
+        .iter()
+        .filter(|n| match &spec.filters {
+            Some(f) => matches_filter(f, n, ctx),
+            None => true,
+        })
+        .collect();
+
+    let views = spec
+        .views
+        .iter()
+        .map(|view| resolve_view(spec, view, &candidates, ctx))
+        .collect();
+
+    ResolvedBase { views }
+}
+
+fn resolve_view(
+    spec: &BaseSpec,
+    view: &ViewSpec,
+    notes: &[&NoteContext],
+    ctx: &EvalContext,
+) -> ResolvedView {
+    let mut matched: Vec<&NoteContext> = notes
+        .iter()
+        .filter(|n| match &view.filters {
+            Some(f) => matches_filter(f, n, ctx),
+            None => true,
+        })
+        .copied()
+        .collect();
+
+    // Stable sorts applied last-to-first give multi-key ordering; missing values stay last
+    for sort_spec in view.sort.iter().rev() {
+        let prop = &sort_spec.property;
+        let desc = sort_spec.direction == SortDirection::Desc;
+        matched.sort_by(|a, b| {
+            let (a, b) = (
+                sort_key(a, prop, ctx.options),
+                sort_key(b, prop, ctx.options),
+            );
+            let cmp = eval::sort_order(&a, &b);
+            if desc && !matches!(a, Val::Null) && !matches!(b, Val::Null) {
+                cmp.reverse()
+            } else {
+                cmp
+            }
+        });
+    }
+
+    let rows = matched
+        .iter()
+        .map(|note| Row {
+            file: note.rel_path.clone(),
+            name: note.name.clone(),
+            cells: view
+                .order
+                .iter()
+                .map(|col| display_value(note, col, ctx.options))
+                .collect(),
+        })
+        .collect();
+
+    ResolvedView {
+        name: view.name.clone(),
+        view_type: view.view_type.clone(),
+        columns: view.order.clone(),
+        headers: view.order.iter().map(|c| spec.column_header(c)).collect(),
+        rows,
+    }
+}
+
+/// Evaluate a filter tree against a note.
+pub fn matches_filter(filter: &FilterNode, note: &NoteContext, ctx: &EvalContext) -> bool {
+    match filter {
+        FilterNode::And(entries) => entries.iter().all(|e| matches_entry(e, note, ctx)),
+        FilterNode::Or(entries) => entries.iter().any(|e| matches_entry(e, note, ctx)),
+    }
+}
+
+fn matches_entry(entry: &FilterEntry, note: &NoteContext, ctx: &EvalContext) -> bool {
+    match entry {
+        FilterEntry::Expr(s) => eval::eval_filter(s, note, ctx),
+        FilterEntry::Nested(filter) => matches_filter(filter, note, ctx),
+    }
+}
+
+/// Columns of `obsidian-base tasks` output, in order.
+pub const TASK_COLUMNS: &[&str] = &[
+    "line",
+    "status",
+    "text",
+    "section",
+    "due",
+    "scheduled",
+    "start",
+    "completion",
+];
+
+/// Every task in the notes of `ctx`, as a view with one row per task.
+///
+/// Each filter is evaluated with the task bound as `task`; the note stays in scope, so
+/// `!task.closed && status == "closed"` finds open tasks in closed projects. Rows keep vault
+/// order: notes by path, tasks by line.
+pub fn resolve_tasks(filters: &[Expression], ctx: &EvalContext) -> ResolvedView {
+    let mut sorted: Vec<&NoteContext> = ctx.notes.iter().collect();
+    sorted.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+
+    let mut rows = Vec::new();
+    for note in sorted {
+        for task in note.tasks() {
+            let bindings = || vec![("task".to_owned(), Val::from(&task))];
+            if filters
+                .iter()
+                .all(|f| f.eval_with(note, ctx, bindings()).as_bool())
+            {
+                rows.push(Row {
+                    file: note.rel_path.clone(),
+                    name: note.name.clone(),
+                    cells: task_cells(&task),
+                });
+            }
+        }
+    }
+
+    ResolvedView {
+        name: "tasks".to_owned(),
+        view_type: "tasks".to_owned(),
+        columns: TASK_COLUMNS.iter().map(|&c| c.to_owned()).collect(),
+        headers: TASK_COLUMNS.iter().map(|&c| c.to_owned()).collect(),
+        rows,
+    }
+}
+
+/// One cell per `TASK_COLUMNS` entry.
+fn task_cells(task: &Task) -> Vec<serde_json::Value> {
+    vec![
+        json!(task.line),
+        json!(task.status.to_string()),
+        json!(task.text),
+        json!(task.section),
+        json!(task.due),
+        json!(task.scheduled),
+        json!(task.start),
+        json!(task.completion),
+    ]
+}
+
+// ─── Diff ───────────────────────────────────────────────────
+
+/// A JSONL row object as emitted by `ResolvedView::row_json`.
+pub type RowObject = serde_json::Map<String, serde_json::Value>;
+
+/// A row that entered, left or changed within a view between two resolutions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowChange {
+    pub kind: ChangeKind,
+    pub view: String,
+    /// The current row (the previous one for `Removed`).
+    pub row: RowObject,
+    /// Column values that differ as `(old, new)`; empty unless `Changed`.
+    pub columns: BTreeMap<String, (serde_json::Value, serde_json::Value)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl RowChange {
+    /// The row object with a `change` key (`added`, `removed`, `changed`) and, for changed
+    /// rows, a `changes` object mapping each differing column to `{"old": …, "new": …}`.
+    pub fn to_json(&self) -> RowObject {
+        let kind = match self.kind {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        };
+        let mut obj = RowObject::new();
+        obj.insert("change".into(), json!(kind));
+        obj.extend(self.row.clone());
+        if !self.columns.is_empty() {
+            let changes: RowObject = self
+                .columns
+                .iter()
+                .map(|(k, (old, new))| (k.clone(), json!({"old": old, "new": new})))
+                .collect();
+            obj.insert("changes".into(), serde_json::Value::Object(changes));
+        }
+        obj
+    }
+}
+
+/// All rows of a resolution in JSONL form — the snapshot format.
+pub fn snapshot(resolved: &ResolvedBase) -> Vec<RowObject> {
+    resolved
+        .views
+        .iter()
+        .flat_map(|v| v.rows.iter().map(|r| v.row_json(r)))
+        .collect()
+}
+
+/// Read a JSONL snapshot written from `snapshot` (or plain `obsidian-base` output).
+pub fn read_snapshot(path: &Path) -> Result<Vec<RowObject>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{}:{}: invalid snapshot row: {e}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Changes between two resolutions of the same base.
+pub fn diff(old: &ResolvedBase, new: &ResolvedBase) -> Vec<RowChange> {
+    diff_rows(&snapshot(old), &snapshot(new))
+}
+
+/// Changes since a snapshot, counting only snapshot rows of views present in `resolved`,
+/// so a snapshot of every view can be compared against a single-view resolution.
+pub fn diff_snapshot(old: &[RowObject], resolved: &ResolvedBase) -> Vec<RowChange> {
+    let old: Vec<RowObject> = old
+        .iter()
+        .filter(|row| {
+            let view = row.get("view").and_then(|v| v.as_str());
+            resolved.views.iter().any(|v| Some(v.name.as_str()) == view)
+        })
+        .cloned()
+        .collect();
+    diff_rows(&old, &snapshot(resolved))
+}
+
+/// Compare two sets of row objects, matching rows by `view` and `file`.
+///
+/// Changes are grouped by view in the order views first appear; within a view, added
+/// and changed rows follow the new order, then removed rows follow the old order.
+pub fn diff_rows(old: &[RowObject], new: &[RowObject]) -> Vec<RowChange> {
+    let key = |row: &RowObject| {
+        let field = |k: &str| row.get(k).and_then(|v| v.as_str()).unwrap_or("").to_owned();
+        (field("view"), field("file"))
+    };
+    let old_by_key: HashMap<(String, String), &RowObject> =
+        old.iter().map(|r| (key(r), r)).collect();
+    let new_keys: HashSet<(String, String)> = new.iter().map(key).collect();
+
+    let mut views: Vec<String> = Vec::new();
+    for (view, _) in new.iter().chain(old).map(key) {
+        if !views.contains(&view) {
+            views.push(view);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for view in &views {
+        for row in new.iter().filter(|r| &key(r).0 == view) {
+            let Some(previous) = old_by_key.get(&key(row)) else {
+                changes.push(RowChange {
+                    kind: ChangeKind::Added,
+                    view: view.clone(),
+                    row: row.clone(),
+                    columns: BTreeMap::new(),
+                });
+                continue;
+            };
+            let columns = changed_columns(previous, row);
+            if !columns.is_empty() {
+                changes.push(RowChange {
+                    kind: ChangeKind::Changed,
+                    view: view.clone(),
+                    row: row.clone(),
+                    columns,
+                });
+            }
+        }
+        for row in old.iter().filter(|r| &key(r).0 == view) {
+            if !new_keys.contains(&key(row)) {
+                changes.push(RowChange {
+                    kind: ChangeKind::Removed,
+                    view: view.clone(),
+                    row: row.clone(),
+                    columns: BTreeMap::new(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn changed_columns(
+    old: &RowObject,
+    new: &RowObject,
+) -> BTreeMap<String, (serde_json::Value, serde_json::Value)> {
+    let null = serde_json::Value::Null;
+    old.keys()
+        .chain(new.keys())
+        .filter(|k| *k != "view" && *k != "file")
+        .filter_map(|k| {
+            let (a, b) = (old.get(k).unwrap_or(&null), new.get(k).unwrap_or(&null));
+            (a != b).then(|| (k.clone(), (a.clone(), b.clone())))
+        })
+        .collect()
+}
+
+// ─── Explain ────────────────────────────────────────────────
+
+/// How a base's filters evaluated for one note.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub file: String,
+    /// Top-level filters; `None` when the base has none.
+    pub filters: Option<FilterTrace>,
+    pub views: Vec<ViewExplanation>,
+}
+
+/// Filter evaluation of a single view. `matched` includes the top-level filters.
+#[derive(Debug, Clone)]
+pub struct ViewExplanation {
+    pub name: String,
+    pub filters: Option<FilterTrace>,
+    pub matched: bool,
+}
+
+/// Evaluated `and`/`or` node.
+#[derive(Debug, Clone)]
+pub struct FilterTrace {
+    pub combinator: Combinator,
+    pub passed: bool,
+    pub entries: Vec<EntryTrace>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+    And,
+    Or,
+}
+
+/// Evaluated filter entry. `trace` is `None` for expressions that fail to parse.
+#[derive(Debug, Clone)]
+pub enum EntryTrace {
+    Expr {
+        expr: String,
+        passed: bool,
+        trace: Option<eval::Trace>,
+    },
+    Nested(FilterTrace),
+}
+
+impl EntryTrace {
+    pub fn passed(&self) -> bool {
+        match self {
+            EntryTrace::Expr { passed, .. } => *passed,
+            EntryTrace::Nested(node) => node.passed,
+        }
+    }
+}
+
+impl Explanation {
+    /// Whether the top-level filters let the note through.
+    pub fn passes_filters(&self) -> bool {
+        self.filters.as_ref().is_none_or(|f| f.passed)
+    }
+}
+
+/// Evaluate every filter of a base against one note, keeping intermediate values.
+///
+/// Unlike `resolve`, nothing short-circuits: all entries and views are evaluated
+/// even after a rejection, so the full picture is visible.
+pub fn explain(spec: &BaseSpec, note: &NoteContext, ctx: &EvalContext) -> Explanation {
+    let filters = spec.filters.as_ref().map(|f| trace_filter(f, note, ctx));
+    let top_passed = filters.as_ref().is_none_or(|f| f.passed);
+
+    let views = spec
+        .views
+        .iter()
+        .map(|view| {
+            let filters = view.filters.as_ref().map(|f| trace_filter(f, note, ctx));
+            let matched = top_passed && filters.as_ref().is_none_or(|f| f.passed);
+            ViewExplanation {
+                name: view.name.clone(),
+                filters,
+                matched,
+            }
+        })
+        .collect();
+
+    Explanation {
+        file: note.rel_path.clone(),
+        filters,
+        views,
+    }
+}
+
+fn trace_filter(filter: &FilterNode, note: &NoteContext, ctx: &EvalContext) -> FilterTrace {
+    let (combinator, entries) = match filter {
+        FilterNode::And(entries) => (Combinator::And, entries),
+        FilterNode::Or(entries) => (Combinator::Or, entries),
+    };
+    let entries: Vec<EntryTrace> = entries
+        .iter()
+        .map(|entry| match entry {
+            FilterEntry::Expr(s) => {
+                let trace = eval::explain(s, note, ctx);
+                EntryTrace::Expr {
+                    expr: s.clone(),
+                    // Unparseable expressions match, as in `eval_filter`
+                    passed: trace.as_ref().is_none_or(|t| t.value.as_bool()),
+                    trace,
+                }
+            }
+            FilterEntry::Nested(nested) => EntryTrace::Nested(trace_filter(nested, note, ctx)),
+        })
+        .collect();
+    let passed = match combinator {
+        Combinator::And => entries.iter().all(EntryTrace::passed),
+        Combinator::Or => entries.iter().any(EntryTrace::passed),
+    };
+    FilterTrace {
+        combinator,
+        passed,
+        entries,
+    }
+}
+
+/// Strip the `file.`/`property.`/`note.` namespace from a column identifier.
+pub fn column_key(col: &str) -> &str {
+    col.strip_prefix("file.")
+        .or_else(|| col.strip_prefix("property."))
+        .or_else(|| col.strip_prefix("note."))
+        .unwrap_or(col)
+}
+
+/// Value a view sorts on, ordered with `eval::sort_order` so numbers sort numerically.
+fn sort_key(ctx: &NoteContext, prop: &str, options: EvalOptions) -> Val {
+    match prop {
+        "file.name" => Val::Str(ctx.name.clone()),
+        "file.path" => Val::Str(ctx.rel_path.clone()),
+        "file.tags" | "property.tags" => Val::Str(ctx.tags.join(", ")),
+        p => property_value(ctx, p, options).map_or(Val::Null, Val::from),
+    }
+}
+
+/// The frontmatter (or, with `options.inline_fields`, inline) value a column names.
+fn property_value<'n>(ctx: &'n NoteContext, col: &str, options: EvalOptions) -> Option<&'n Value> {
+    let chain: Vec<String> = col.split('.').map(str::to_owned).collect();
+    eval::lookup_property(ctx, &chain, options)
+        .filter(|(_, rest)| rest.is_empty())
+        .map(|(val, _)| val)
+}
+
+/// Display value of a view column for a note, as JSON.
+pub fn display_value(ctx: &NoteContext, col: &str, options: EvalOptions) -> serde_json::Value {
+    match col {
+        "file.name" => json!(ctx.name),
+        "file.path" | "file.fullname" => json!(ctx.rel_path),
+        "file.ext" => json!(ctx.ext),
+        "file.folder" => json!(ctx.folder),
+        "file.tags" | "tags" => json!(ctx.tags),
+        "file.links" => json!(ctx.links),
+        c => property_value(ctx, c, options).map_or(serde_json::Value::Null, yaml_to_json),
+    }
+}
+
+/// Convert a frontmatter value to JSON, keeping numbers, nesting and mappings intact.
+fn yaml_to_json(val: &Value) -> serde_json::Value {
+    match val {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => json!(b),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                json!(i)
+            } else if let Some(u) = n.as_u64() {
+                json!(u)
+            } else {
+                n.as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map_or_else(|| json!(n.to_string()), serde_json::Value::Number)
+            }
+        }
+        Value::String(s) => json!(s),
+        Value::Sequence(seq) => seq.iter().map(yaml_to_json).collect(),
+        Value::Mapping(map) => map
+            .iter()
+            .map(|(k, v)| {
+                let key = match k {
+                    Value::String(s) => s.clone(),
+                    other => match yaml_to_json(other) {
+                        serde_json::Value::String(s) => s,
+                        json => json.to_string(),
+                    },
+                };
+                (key, yaml_to_json(v))
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        Value::Tagged(tagged) => yaml_to_json(&tagged.value),
+    }
+}
@@ -1,4 +1,11 @@
+// Pedantic lints the baseline tests predate
+#![allow(
+    clippy::match_wildcard_for_single_variants,
+    clippy::needless_raw_string_hashes
+)]
+
 use super::*;
+use crate::eval::ThisContext;
 
 #[test]
 fn parse_simple_base() {
@@ -104,3 +111,330 @@ views:
     assert_eq!(spec.views[0].sort[0].property, "property.tags");
     assert_eq!(spec.views[0].sort[0].direction, SortDirection::Desc);
 }
+
+#[test]
+fn column_key_strips_namespace() {
+    assert_eq!(column_key("file.name"), "name");
+    assert_eq!(column_key("property.project.status"), "project.status");
+    assert_eq!(column_key("note.rating"), "rating");
+    assert_eq!(column_key("rating"), "rating");
+}
+
+#[test]
+fn parse_display_names() {
+    let yaml = "
+properties:
+  file.name:
+    displayName: Title
+  note.rating:
+    displayName: Stars
+views:
+  - type: table
+    name: T
+";
+
+    let spec = parse_str(yaml).unwrap();
+    assert_eq!(spec.column_header("file.name"), "Title");
+    assert_eq!(spec.column_header("rating"), "Stars");
+    assert_eq!(spec.column_header("property.rating"), "Stars");
+    assert_eq!(spec.column_header("finished"), "finished");
+}
+
+#[test]
+fn sort_shorthand() {
+    let spec = SortSpec::parse("finished:desc");
+    assert_eq!(spec.property, "finished");
+    assert_eq!(spec.direction, SortDirection::Desc);
+
+    let spec = SortSpec::parse("project.status");
+    assert_eq!(spec.property, "project.status");
+    assert_eq!(spec.direction, SortDirection::Asc);
+}
+
+#[test]
+fn query_builds_single_view() {
+    let spec = BaseSpec::query(
+        vec!["rating >= 4".into(), r#"file.hasTag("type/item")"#.into()],
+        vec![SortSpec::parse("rating:desc")],
+        vec!["file.name".into(), "rating".into()],
+    );
+    assert!(spec.filters.is_none());
+    assert_eq!(spec.views.len(), 1);
+    match &spec.views[0].filters {
+        Some(FilterNode::And(entries)) => assert_eq!(entries.len(), 2),
+        other => panic!("expected And, got {other:?}"),
+    }
+}
+
+#[test]
+fn sort_orders_numbers_numerically() {
+    let book = |name: &str, rating: Option<i64>| {
+        let mut note = NoteContext::from_content(&format!("{name}.md"), "");
+        note.properties = rating
+            .map(|r| ("rating".to_owned(), Value::from(r)))
+            .into_iter()
+            .collect();
+        note
+    };
+    let notes = [
+        book("Ten", Some(10)),
+        book("Unrated", None),
+        book("Nine", Some(9)),
+        book("Hundred", Some(100)),
+    ];
+    let names = |sort: &str| {
+        let spec = BaseSpec::query(Vec::new(), vec![SortSpec::parse(sort)], Vec::new());
+        resolve(&spec, &notes, &ThisContext::default()).views[0]
+            .rows
+            .iter()
+            .map(|r| r.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names("rating"), ["Nine", "Ten", "Hundred", "Unrated"]);
+    assert_eq!(
+        names("note.rating:desc"),
+        ["Hundred", "Ten", "Nine", "Unrated"]
+    );
+}
+
+#[test]
+fn inline_fields_filter_output_and_sort_together() {
+    let task = |name: &str, body: &str| NoteContext::from_content(&format!("{name}.md"), body);
+    let notes = [
+        task("Ten", "effort:: 10\n"),
+        task("Two", "effort:: 2\n"),
+        task("One", "effort:: 1\n"),
+        task("None", "No fields\n"),
+    ];
+    let spec = BaseSpec::query(
+        vec!["note.effort > 1".into()],
+        vec![SortSpec::parse("note.effort")],
+        vec!["file.name".into(), "note.effort".into()],
+    );
+    let this_ctx = ThisContext::default();
+    let rows = |options: EvalOptions| {
+        resolve_with(
+            &spec,
+            &EvalContext::new(&this_ctx, &notes).with_options(options),
+        )
+        .views[0]
+            .rows
+            .iter()
+            .map(|r| (r.name.clone(), r.cells[1].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(rows(EvalOptions::default()), []);
+    assert_eq!(
+        rows(EvalOptions {
+            inline_fields: true
+        }),
+        [("Two".to_owned(), json!(2)), ("Ten".to_owned(), json!(10))]
+    );
+}
+
+#[test]
+fn display_value_keeps_numbers_and_mappings() {
+    let properties: HashMap<String, Value> = serde_yaml::from_str(
+        "scores: [4, 5]\nmixed: [a, 1, true]\nmeta:\n  pages: 412\n  tags: [x]\n",
+    )
+    .unwrap();
+    let mut note = NoteContext::from_content("Book.md", "");
+    note.properties = properties;
+
+    assert_eq!(
+        display_value(&note, "scores", EvalOptions::default()),
+        json!([4, 5])
+    );
+    assert_eq!(
+        display_value(&note, "note.mixed", EvalOptions::default()),
+        json!(["a", 1, true])
+    );
+    assert_eq!(
+        display_value(&note, "meta", EvalOptions::default()),
+        json!({"pages": 412, "tags": ["x"]})
+    );
+}
+
+#[test]
+fn explain_reports_each_level() {
+    let yaml = r#"
+filters:
+  and:
+    - file.ext == "md"
+views:
+  - type: table
+    name: Projects
+    filters:
+      or:
+        - file.hasTag("type/project")
+        - and:
+            - file.name == "Inbox"
+  - type: table
+    name: All
+"#;
+    let spec = parse_str(yaml).unwrap();
+    let note = NoteContext::from_content("Notes/Idea.md", "");
+
+    let exp = explain(
+        &spec,
+        &note,
+        &EvalContext::new(&ThisContext::default(), &[]),
+    );
+    assert!(exp.passes_filters());
+    assert!(!exp.views[0].matched);
+    assert!(exp.views[1].matched);
+
+    let view_filter = exp.views[0].filters.as_ref().unwrap();
+    assert_eq!(view_filter.combinator, Combinator::Or);
+    assert_eq!(view_filter.entries.len(), 2);
+    assert!(matches!(&view_filter.entries[1], EntryTrace::Nested(n) if !n.passed));
+}
+
+fn resolved_view(name: &str, files: &[&str]) -> ResolvedView {
+    ResolvedView {
+        name: name.into(),
+        view_type: "table".into(),
+        columns: Vec::new(),
+        headers: Vec::new(),
+        rows: files
+            .iter()
+            .map(|f| Row {
+                file: (*f).to_owned(),
+                name: f.trim_end_matches(".md").to_owned(),
+                cells: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn diff_reports_added_and_removed_rows() {
+    let old = ResolvedBase {
+        views: vec![resolved_view("Books", &["A.md", "B.md"])],
+    };
+    let new = ResolvedBase {
+        views: vec![
+            resolved_view("Books", &["B.md", "C.md"]),
+            resolved_view("New", &["A.md"]),
+        ],
+    };
+
+    let changes = diff(&old, &new);
+    let summary: Vec<(ChangeKind, &str, &str)> = changes
+        .iter()
+        .map(|c| (c.kind, c.view.as_str(), c.row["file"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (ChangeKind::Added, "Books", "C.md"),
+            (ChangeKind::Removed, "Books", "A.md"),
+            (ChangeKind::Added, "New", "A.md"),
+        ]
+    );
+    assert_eq!(changes[0].to_json()["change"], "added");
+}
+
+fn row_object(value: serde_json::Value) -> RowObject {
+    match value {
+        serde_json::Value::Object(obj) => obj,
+        other => panic!("expected object, got {other}"),
+    }
+}
+
+#[test]
+fn diff_rows_reports_changed_columns() {
+    let old = vec![row_object(serde_json::json!({
+        "view": "Books", "file": "A.md", "name": "A", "rating": 3, "status": "reading"
+    }))];
+    let new = vec![row_object(serde_json::json!({
+        "view": "Books", "file": "A.md", "name": "A", "rating": 4
+    }))];
+
+    let changes = diff_rows(&old, &new);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Changed);
+    assert_eq!(
+        changes[0].columns.keys().collect::<Vec<_>>(),
+        vec!["rating", "status"]
+    );
+
+    let json = changes[0].to_json();
+    assert_eq!(json["change"], "changed");
+    assert_eq!(json["changes"]["rating"]["old"], 3);
+    assert_eq!(json["changes"]["rating"]["new"], 4);
+    assert_eq!(json["changes"]["status"]["new"], serde_json::Value::Null);
+}
+
+#[test]
+fn diff_rows_ignores_unchanged() {
+    let rows = vec![row_object(serde_json::json!({
+        "view": "Books", "file": "A.md", "name": "A"
+    }))];
+    assert!(diff_rows(&rows, &rows).is_empty());
+}
+
+#[test]
+fn diff_snapshot_ignores_views_not_resolved() {
+    let everything = ResolvedBase {
+        views: vec![
+            resolved_view("Books", &["A.md"]),
+            resolved_view("Favourites", &["A.md", "B.md"]),
+        ],
+    };
+    let books = ResolvedBase {
+        views: vec![resolved_view("Books", &["A.md", "C.md"])],
+    };
+
+    let changes = diff_snapshot(&snapshot(&everything), &books);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Added);
+    assert_eq!(changes[0].row["file"], "C.md");
+}
+
+#[test]
+fn read_snapshot_reports_bad_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snap.jsonl");
+    fs::write(&path, "{\"view\":\"V\",\"file\":\"A.md\"}\n\nnot json\n").unwrap();
+    let err = read_snapshot(&path).unwrap_err();
+    assert!(err.contains("snap.jsonl:3: invalid snapshot row"), "{err}");
+
+    fs::write(&path, "{\"view\":\"V\",\"file\":\"A.md\"}\n").unwrap();
+    assert_eq!(read_snapshot(&path).unwrap().len(), 1);
+}
+
+#[test]
+fn resolve_tasks_binds_task_next_to_note_properties() {
+    let project = |name: &str, status: &str, body: &str| {
+        let mut note = NoteContext::from_content(&format!("Projects/{name}.md"), body);
+        note.properties = HashMap::from([("status".to_owned(), Value::from(status))]);
+        note
+    };
+    let notes = [
+        project(
+            "Beta",
+            "closed",
+            "# Beta\n- [ ] Left over 📅 2026-01-10\n- [x] Done\n",
+        ),
+        project("Alpha", "active", "# Alpha\n- [ ] Next step\n"),
+    ];
+    let filters = [Expression::parse("!task.closed && status == \"closed\"").unwrap()];
+    let this_ctx = ThisContext::default();
+
+    let view = resolve_tasks(&filters, &EvalContext::new(&this_ctx, &notes));
+    assert_eq!(view.rows.len(), 1);
+    let row = view.row_json(&view.rows[0]);
+    assert_eq!(row["file"], "Projects/Beta.md");
+    assert_eq!(row["text"], "Left over");
+    assert_eq!(row["due"], "2026-01-10");
+    assert_eq!(row["line"], 2);
+
+    let all = resolve_tasks(&[], &EvalContext::new(&this_ctx, &notes));
+    assert_eq!(
+        all.rows.iter().map(|r| r.file.as_str()).collect::<Vec<_>>(),
+        ["Projects/Alpha.md", "Projects/Beta.md", "Projects/Beta.md"]
+    );
+}
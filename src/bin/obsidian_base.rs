@@ -1,24 +1,79 @@
-use forge_obsidian::base::{self, FilterEntry, FilterNode};
-use forge_obsidian::eval::{self, ThisContext};
+use forge_obsidian::base::{self, BaseSpec, ResolvedBase, SortSpec};
+use forge_obsidian::eval::{self, EvalContext, EvalOptions, ThisContext};
 use forge_obsidian::note::{self, NoteContext};
-use forge_obsidian::vault;
-use serde_json::json;
+use forge_obsidian::output::{self, Format};
+use forge_obsidian::vault::{self, LinkIndex};
+use notify::{RecursiveMode, Watcher};
 use std::env;
-use std::path::PathBuf;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
-struct Args {
+enum Command {
+    Resolve(ResolveArgs),
+    Query(QueryArgs),
+    Tasks(TasksArgs),
+    Repl(ReplArgs),
+}
+
+struct ResolveArgs {
     base_path: PathBuf,
     view_filter: Option<String>,
-    paths_only: bool,
+    format: Format,
+    explain: bool,
+    note_filter: Option<String>,
+    watch: bool,
+    changes_only: bool,
+    snapshot_path: Option<PathBuf>,
+    diff_against: Option<PathBuf>,
+    options: EvalOptions,
+}
+
+struct QueryArgs {
+    vault_root: PathBuf,
+    filters: Vec<String>,
+    sort: Vec<SortSpec>,
+    columns: Vec<String>,
+    format: Format,
+    options: EvalOptions,
+}
+
+struct TasksArgs {
+    vault_root: PathBuf,
+    filters: Vec<String>,
+    format: Format,
+    options: EvalOptions,
+}
+
+struct ReplArgs {
+    vault_root: PathBuf,
+    options: EvalOptions,
+}
+
+fn parse_args() -> Result<Command, ExitCode> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("query") => parse_query_args(&args[1..]).map(Command::Query),
+        Some("tasks") => parse_tasks_args(&args[1..]).map(Command::Tasks),
+        Some("repl") => parse_repl_args(&args[1..]).map(Command::Repl),
+        _ => parse_resolve_args(&args).map(Command::Resolve),
+    }
 }
 
-fn parse_args() -> Result<Args, ExitCode> {
-    let args: Vec<String> = env::args().collect();
+fn parse_resolve_args(args: &[String]) -> Result<ResolveArgs, ExitCode> {
     let mut base_path = None;
     let mut view_filter = None;
-    let mut paths_only = false;
-    let mut i = 1;
+    let mut format = None;
+    let mut explain = false;
+    let mut note_filter = None;
+    let mut watch = false;
+    let mut changes_only = false;
+    let mut snapshot_path = None;
+    let mut diff_against = None;
+    let mut options = EvalOptions::default();
+    let mut i = 0;
 
     while i < args.len() {
         match args[i].as_str() {
@@ -26,7 +81,27 @@ fn parse_args() -> Result<Args, ExitCode> {
                 i += 1;
                 view_filter = args.get(i).cloned();
             }
-            "--paths" => paths_only = true,
+            "--format" => {
+                i += 1;
+                format = Some(parse_format(args.get(i))?);
+            }
+            "--paths" => format = Some(Format::Paths),
+            "--explain" => explain = true,
+            "--watch" => watch = true,
+            "--changes" => changes_only = true,
+            "--inline-fields" => options.inline_fields = true,
+            "--snapshot" => {
+                i += 1;
+                snapshot_path = args.get(i).map(PathBuf::from);
+            }
+            "--diff" => {
+                i += 1;
+                diff_against = args.get(i).map(PathBuf::from);
+            }
+            "--note" => {
+                i += 1;
+                note_filter = args.get(i).cloned();
+            }
             "--help" | "-h" => {
                 print_usage();
                 return Err(ExitCode::SUCCESS);
@@ -41,6 +116,32 @@ fn parse_args() -> Result<Args, ExitCode> {
         i += 1;
     }
 
+    // Explanations have their own text output and are never watched or snapshotted
+    if explain {
+        let conflicts = [
+            ("--watch", watch),
+            ("--snapshot", snapshot_path.is_some()),
+            ("--diff", diff_against.is_some()),
+            ("--format/--paths", format.is_some()),
+        ];
+        if let Some((flag, _)) = conflicts.iter().find(|(_, set)| *set) {
+            eprintln!("Error: {flag} cannot be combined with --explain");
+            return Err(ExitCode::from(1));
+        }
+    }
+    if diff_against.is_some() && !matches!(format, None | Some(Format::Jsonl | Format::Json)) {
+        eprintln!("Error: --diff emits row changes as jsonl or json, not other formats");
+        return Err(ExitCode::from(1));
+    }
+    if changes_only && !watch {
+        eprintln!("Error: --changes only applies to --watch (use --diff for a one-off comparison)");
+        return Err(ExitCode::from(1));
+    }
+    if watch && (snapshot_path.is_some() || diff_against.is_some()) {
+        eprintln!("Error: --snapshot and --diff cannot be combined with --watch (use --changes)");
+        return Err(ExitCode::from(1));
+    }
+
     let Some(path_str) = base_path else {
         eprintln!("Error: no .base file specified");
         print_usage();
@@ -52,25 +153,202 @@ fn parse_args() -> Result<Args, ExitCode> {
         ExitCode::from(1)
     })?;
 
-    Ok(Args {
+    Ok(ResolveArgs {
         base_path,
         view_filter,
-        paths_only,
+        format: format.unwrap_or(Format::Jsonl),
+        explain,
+        note_filter,
+        watch,
+        changes_only,
+        snapshot_path,
+        diff_against,
+        options,
     })
 }
 
-fn main() -> ExitCode {
-    let args = match parse_args() {
-        Ok(a) => a,
-        Err(code) => return code,
+fn parse_query_args(args: &[String]) -> Result<QueryArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut filters = Vec::new();
+    let mut sort = Vec::new();
+    let mut columns = Vec::new();
+    let mut format = Format::Jsonl;
+    let mut options = EvalOptions::default();
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--filter" => {
+                i += 1;
+                filters.extend(args.get(i).cloned());
+            }
+            "--sort" => {
+                i += 1;
+                sort.extend(args.get(i).map(|s| SortSpec::parse(s)));
+            }
+            "--columns" => {
+                i += 1;
+                columns.extend(
+                    args.get(i)
+                        .into_iter()
+                        .flat_map(|s| s.split(','))
+                        .map(str::trim)
+                        .filter(|c| !c.is_empty())
+                        .map(String::from),
+                );
+            }
+            "--format" => {
+                i += 1;
+                format = parse_format(args.get(i))?;
+            }
+            "--paths" => format = Format::Paths,
+            "--inline-fields" => options.inline_fields = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    let vault_root = vault_root_arg(vault_dir)?;
+
+    Ok(QueryArgs {
+        vault_root,
+        filters,
+        sort,
+        columns,
+        format,
+        options,
+    })
+}
+
+fn parse_tasks_args(args: &[String]) -> Result<TasksArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut filters = Vec::new();
+    let mut format = Format::Jsonl;
+    let mut options = EvalOptions::default();
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--filter" => {
+                i += 1;
+                filters.extend(args.get(i).cloned());
+            }
+            "--format" => {
+                i += 1;
+                format = parse_format(args.get(i))?;
+            }
+            "--paths" => format = Format::Paths,
+            "--inline-fields" => options.inline_fields = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    Ok(TasksArgs {
+        vault_root: vault_root_arg(vault_dir)?,
+        filters,
+        format,
+        options,
+    })
+}
+
+fn parse_repl_args(args: &[String]) -> Result<ReplArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut options = EvalOptions::default();
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--inline-fields" => options.inline_fields = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    Ok(ReplArgs {
+        vault_root: vault_root_arg(vault_dir)?,
+        options,
+    })
+}
+
+/// Resolve `--vault <dir>`, falling back to the vault containing the cwd.
+fn vault_root_arg(vault_dir: Option<String>) -> Result<PathBuf, ExitCode> {
+    match vault_dir {
+        Some(dir) => std::fs::canonicalize(&dir).map_err(|e| {
+            eprintln!("Cannot resolve {dir}: {e}");
+            ExitCode::from(1)
+        }),
+        None => vault::find_vault_from_cwd().ok_or_else(|| {
+            eprintln!("Cannot find vault root (pass --vault or run inside a vault)");
+            ExitCode::from(1)
+        }),
+    }
+}
+
+fn parse_format(value: Option<&String>) -> Result<Format, ExitCode> {
+    let Some(name) = value else {
+        eprintln!("Error: --format needs a value");
+        return Err(ExitCode::from(1));
     };
+    name.parse().map_err(|e| {
+        eprintln!("Error: {e}");
+        ExitCode::from(1)
+    })
+}
 
+fn main() -> ExitCode {
+    match parse_args() {
+        Ok(Command::Resolve(args)) => run_resolve(&args),
+        Ok(Command::Query(args)) => run_query(args),
+        Ok(Command::Tasks(args)) => run_tasks(&args),
+        Ok(Command::Repl(args)) => run_repl(&args),
+        Err(code) => code,
+    }
+}
+
+fn run_resolve(args: &ResolveArgs) -> ExitCode {
     let Some(vault_root) = vault::find_vault(args.base_path.to_str().unwrap_or("")) else {
         eprintln!("Cannot find vault root (no .obsidian/ directory in parent chain)");
         return ExitCode::from(1);
     };
 
-    let spec = match base::parse_file(&args.base_path) {
+    let spec = match load_spec(args) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("{e}");
@@ -81,159 +359,430 @@ fn main() -> ExitCode {
     let notes = note::walk_vault(&vault_root);
     let this_ctx = ThisContext::from_base_path(&vault_root, &args.base_path);
 
-    // Pre-filter with top-level filters
-    let notes: Vec<&NoteContext> = notes
-        .iter()
-        .filter(|n| match &spec.filters {
-            Some(f) => matches_filter(f, n, &this_ctx),
-            None => true,
-        })
-        .collect();
+    if args.explain {
+        let ctx = EvalContext::new(&this_ctx, &notes).with_options(args.options);
+        return run_explain(&spec, &ctx, args.note_filter.as_deref());
+    }
 
-    // Process each view
-    for view in &spec.views {
-        if let Some(ref name) = args.view_filter {
-            if !view.name.eq_ignore_ascii_case(name) {
-                continue;
-            }
-        }
-
-        let mut matched: Vec<&NoteContext> = notes
-            .iter()
-            .filter(|n| match &view.filters {
-                Some(f) => matches_filter(f, n, &this_ctx),
-                None => true,
-            })
-            .copied()
-            .collect();
-
-        // Apply sort
-        for sort_spec in view.sort.iter().rev() {
-            let prop = &sort_spec.property;
-            let desc = sort_spec.direction == base::SortDirection::Desc;
-            matched.sort_by(|a, b| {
-                let cmp = get_sort_key(a, prop).cmp(&get_sort_key(b, prop));
-                if desc {
-                    cmp.reverse()
-                } else {
-                    cmp
-                }
-            });
-        }
+    if args.watch {
+        return run_watch(args, &vault_root, spec, notes, &this_ctx);
+    }
 
-        for matched_note in &matched {
-            if args.paths_only {
-                println!("{}", matched_note.rel_path);
+    let resolved = base::resolve_with(
+        &spec,
+        &EvalContext::new(&this_ctx, &notes).with_options(args.options),
+    );
+
+    match &args.diff_against {
+        Some(path) => {
+            // A missing snapshot is a first run: every row counts as added
+            let old = if path.exists() {
+                match base::read_snapshot(path) {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        eprintln!("{e}");
+                        return ExitCode::from(1);
+                    }
+                }
             } else {
-                print_jsonl(view, matched_note);
+                Vec::new()
+            };
+            let changes = base::diff_snapshot(&old, &resolved);
+            if args.format == Format::Json {
+                let rows: Vec<_> = changes.iter().map(base::RowChange::to_json).collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&rows).unwrap_or_default()
+                );
+            } else {
+                print!("{}", changes_jsonl(&changes));
             }
         }
+        None => print!("{}", render(&resolved, args.format, &notes)),
+    }
+
+    if let Some(path) = &args.snapshot_path {
+        if let Err(e) = std::fs::write(path, output::render(&resolved, Format::Jsonl)) {
+            eprintln!("Cannot write {}: {e}", path.display());
+            return ExitCode::from(1);
+        }
     }
 
     ExitCode::SUCCESS
 }
 
-fn print_jsonl(view: &base::ViewSpec, ctx: &NoteContext) {
-    let mut obj = serde_json::Map::new();
-    obj.insert("view".into(), json!(view.name));
-    obj.insert("file".into(), json!(ctx.rel_path));
-    obj.insert("name".into(), json!(ctx.name));
+/// Render rows, linking notes in Markdown by path when the vault has another with the
+/// same name.
+fn render(resolved: &ResolvedBase, format: Format, notes: &[NoteContext]) -> String {
+    let links = LinkIndex::from_paths(notes.iter().map(|n| n.rel_path.clone()));
+    output::render_linked(resolved, format, &links)
+}
+
+fn changes_jsonl(changes: &[base::RowChange]) -> String {
+    changes
+        .iter()
+        .map(|c| serde_json::to_string(&c.to_json()).unwrap_or_default() + "\n")
+        .collect()
+}
+
+/// Parse the base file, keeping only the `--view` view when given.
+fn load_spec(args: &ResolveArgs) -> Result<BaseSpec, String> {
+    let mut spec = base::parse_file(&args.base_path)?;
+    if let Some(ref name) = args.view_filter {
+        spec.views.retain(|v| v.name.eq_ignore_ascii_case(name));
+    }
+    Ok(spec)
+}
+
+// ─── Watch ──────────────────────────────────────────────────
+
+/// Quiet period that groups a burst of file events (e.g. an editor save) into one update.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
-    for col in &view.order {
-        let key = col
-            .strip_prefix("file.")
-            .or_else(|| col.strip_prefix("property."))
-            .or_else(|| col.strip_prefix("note."))
-            .unwrap_or(col);
-        if key == "name" || obj.contains_key(key) {
+/// Longest a batch waits for quiet, so a steady stream of writes (a sync client) still
+/// produces updates.
+const WATCH_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Emit the resolution, then re-emit it (or only the changed rows) whenever the vault
+/// or the base file changes. Notes are updated in place instead of re-walking the vault.
+fn run_watch(
+    args: &ResolveArgs,
+    vault_root: &Path,
+    mut spec: BaseSpec,
+    mut notes: Vec<NoteContext>,
+    this_ctx: &ThisContext,
+) -> ExitCode {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Cannot watch {}: {e}", vault_root.display());
+            return ExitCode::from(1);
+        }
+    };
+    if let Err(e) = watcher.watch(vault_root, RecursiveMode::Recursive) {
+        eprintln!("Cannot watch {}: {e}", vault_root.display());
+        return ExitCode::from(1);
+    }
+
+    let mut previous = base::resolve_with(
+        &spec,
+        &EvalContext::new(this_ctx, &notes).with_options(args.options),
+    );
+    if !args.changes_only {
+        emit(&render(&previous, args.format, &notes));
+    }
+
+    while let Ok(first) = rx.recv() {
+        let mut events = vec![first];
+        let deadline = Instant::now() + WATCH_MAX_DELAY;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(WATCH_DEBOUNCE.min(left)) {
+                Ok(event) => events.push(event),
+                Err(_) => break,
+            }
+        }
+
+        let mut changed = false;
+        for event in events {
+            // Created folders are rescanned as a whole by `refresh_path`: notes moved in
+            // before notify watched the folder have no events of their own. When events
+            // may have been lost, rescan what the error names, or the whole vault.
+            let paths = match event {
+                Ok(ev) if ev.need_rescan() => vec![vault_root.to_path_buf()],
+                Ok(ev) => ev.paths,
+                Err(e) => {
+                    eprintln!("warning: watch error: {e}");
+                    if e.paths.is_empty() {
+                        vec![vault_root.to_path_buf()]
+                    } else {
+                        e.paths
+                    }
+                }
+            };
+            for path in &paths {
+                if path == &args.base_path {
+                    match load_spec(args) {
+                        Ok(s) => {
+                            spec = s;
+                            changed = true;
+                        }
+                        Err(e) => eprintln!("warning: {e}"),
+                    }
+                } else {
+                    changed |= note::refresh_path(&mut notes, vault_root, path);
+                }
+            }
+        }
+        if !changed {
             continue;
         }
-        let val = get_display_value(ctx, col);
-        if !val.is_null() {
-            obj.insert(key.to_owned(), val);
+
+        let current = base::resolve_with(
+            &spec,
+            &EvalContext::new(this_ctx, &notes).with_options(args.options),
+        );
+        if args.changes_only {
+            emit(&changes_jsonl(&base::diff(&previous, &current)));
+        } else {
+            emit(&render(&current, args.format, &notes));
         }
+        previous = current;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn emit(text: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = stdout.write_all(text.as_bytes());
+    let _ = stdout.flush();
+}
+
+fn run_explain(spec: &BaseSpec, ctx: &EvalContext, note_filter: Option<&str>) -> ExitCode {
+    let selected: Vec<&NoteContext> = ctx
+        .notes
+        .iter()
+        .filter(|n| note_filter.is_none_or(|f| note_matches(n, f)))
+        .collect();
+
+    if let (Some(f), true) = (note_filter, selected.is_empty()) {
+        eprintln!("No note matches {f}");
+        return ExitCode::from(1);
     }
 
-    println!("{}", serde_json::to_string(&obj).unwrap_or_default());
+    let reports: Vec<String> = selected
+        .iter()
+        .map(|n| output::render_explanation(&base::explain(spec, n, ctx)))
+        .collect();
+    print!("{}", reports.join("\n"));
+
+    ExitCode::SUCCESS
 }
 
-fn matches_filter(filter: &FilterNode, ctx: &NoteContext, this_ctx: &ThisContext) -> bool {
-    match filter {
-        FilterNode::And(entries) => entries.iter().all(|e| matches_entry(e, ctx, this_ctx)),
-        FilterNode::Or(entries) => entries.iter().any(|e| matches_entry(e, ctx, this_ctx)),
+/// Match a note by vault-relative path (with or without `.md`) or by name.
+fn note_matches(note: &NoteContext, query: &str) -> bool {
+    note.rel_path == query || note.rel_path.strip_suffix(".md") == Some(query) || note.name == query
+}
+
+fn run_query(args: QueryArgs) -> ExitCode {
+    for source in &args.filters {
+        if let Err(e) = eval::Expression::parse(source) {
+            eprintln!("{}", e.caret(source));
+            return ExitCode::from(1);
+        }
     }
+
+    let spec = BaseSpec::query(args.filters, args.sort, args.columns);
+    let notes = note::walk_vault(&args.vault_root);
+    let this_ctx = ThisContext::default();
+    let resolved = base::resolve_with(
+        &spec,
+        &EvalContext::new(&this_ctx, &notes).with_options(args.options),
+    );
+
+    print!("{}", render(&resolved, args.format, &notes));
+
+    ExitCode::SUCCESS
 }
 
-fn matches_entry(entry: &FilterEntry, ctx: &NoteContext, this_ctx: &ThisContext) -> bool {
-    match entry {
-        FilterEntry::Expr(s) => eval::eval_filter(s, ctx, this_ctx),
-        FilterEntry::Nested(filter) => matches_filter(filter, ctx, this_ctx),
+fn run_tasks(args: &TasksArgs) -> ExitCode {
+    let mut filters = Vec::new();
+    for source in &args.filters {
+        match eval::Expression::parse(source) {
+            Ok(expr) => filters.push(expr),
+            Err(e) => {
+                eprintln!("{}", e.caret(source));
+                return ExitCode::from(1);
+            }
+        }
     }
+
+    let notes = note::walk_vault(&args.vault_root);
+    let this_ctx = ThisContext::default();
+    let resolved = ResolvedBase {
+        views: vec![base::resolve_tasks(
+            &filters,
+            &EvalContext::new(&this_ctx, &notes).with_options(args.options),
+        )],
+    };
+    print!("{}", render(&resolved, args.format, &notes));
+
+    ExitCode::SUCCESS
+}
+
+// ─── REPL ───────────────────────────────────────────────────
+
+const REPL_HELP: &str = "\
+Commands:
+  <expr>           Evaluate an expression against the selected note
+  :note <path>     Select a note (vault-relative path or name)
+  :filter <expr>   List every note the expression matches
+  :help            Show this help
+  :quit            Exit";
+
+/// Interactive session over a vault loaded once.
+struct Repl {
+    notes: Vec<NoteContext>,
+    this_ctx: ThisContext,
+    options: EvalOptions,
+    selected: Option<usize>,
 }
 
-fn get_sort_key(ctx: &NoteContext, prop: &str) -> String {
-    match prop {
-        "file.name" => ctx.name.clone(),
-        "file.path" => ctx.rel_path.clone(),
-        "file.tags" | "property.tags" => ctx.tags.join(", "),
-        p => {
-            let key = p
-                .strip_prefix("property.")
-                .or_else(|| p.strip_prefix("note."))
-                .unwrap_or(p);
-            ctx.get_property(key)
-                .map(|v| match v {
-                    serde_yaml::Value::String(s) => s.clone(),
-                    other => serde_yaml::to_string(other).unwrap_or_default(),
-                })
-                .unwrap_or_default()
+impl Repl {
+    /// Handle one input line, returning the text to print.
+    fn handle(&mut self, line: &str) -> String {
+        let line = line.trim();
+        if !line.starts_with(':') {
+            return self.evaluate(line);
+        }
+        // The command is the whole first word, so `:notes` is not `:note`
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match command {
+            ":note" => self.select(rest.trim()),
+            ":filter" => self.filter(rest.trim()),
+            ":help" | ":h" => REPL_HELP.to_owned(),
+            _ => format!("Unknown command: {command} (try :help)"),
+        }
+    }
+
+    fn select(&mut self, query: &str) -> String {
+        match self.notes.iter().position(|n| note_matches(n, query)) {
+            Some(i) => {
+                self.selected = Some(i);
+                format!("Selected {}", self.notes[i].rel_path)
+            }
+            None => format!("No note matches {query}"),
+        }
+    }
+
+    fn filter(&self, source: &str) -> String {
+        match eval::Expression::parse(source) {
+            Ok(expr) => {
+                let ctx = EvalContext::new(&self.this_ctx, &self.notes).with_options(self.options);
+                let paths: Vec<&str> = self
+                    .notes
+                    .iter()
+                    .filter(|n| expr.matches(n, &ctx))
+                    .map(|n| n.rel_path.as_str())
+                    .collect();
+                let count = format!("({} notes)", paths.len());
+                paths
+                    .into_iter()
+                    .chain([count.as_str()])
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Err(e) => e.caret(source),
+        }
+    }
+
+    fn evaluate(&self, source: &str) -> String {
+        let Some(i) = self.selected else {
+            return "No note selected (use :note <path>)".to_owned();
+        };
+        match eval::Expression::parse(source) {
+            Ok(expr) => {
+                let ctx = EvalContext::new(&self.this_ctx, &self.notes).with_options(self.options);
+                expr.eval(&self.notes[i], &ctx).to_string()
+            }
+            Err(e) => e.caret(source),
         }
     }
 }
 
-fn get_display_value(ctx: &NoteContext, col: &str) -> serde_json::Value {
-    match col {
-        "file.name" => json!(ctx.name),
-        "file.path" | "file.fullname" => json!(ctx.rel_path),
-        "file.ext" => json!(ctx.ext),
-        "file.folder" => json!(ctx.folder),
-        "file.tags" | "tags" => json!(ctx.tags),
-        "file.links" => json!(ctx.links),
-        c => {
-            let key = c
-                .strip_prefix("property.")
-                .or_else(|| c.strip_prefix("note."))
-                .unwrap_or(c);
-            match ctx.get_property(key) {
-                Some(serde_yaml::Value::String(s)) => json!(s),
-                Some(serde_yaml::Value::Bool(b)) => json!(b),
-                Some(serde_yaml::Value::Number(n)) => {
-                    if let Some(i) = n.as_i64() {
-                        json!(i)
-                    } else if let Some(f) = n.as_f64() {
-                        json!(f)
-                    } else {
-                        json!(n.to_string())
-                    }
-                }
-                Some(serde_yaml::Value::Sequence(seq)) => {
-                    let items: Vec<&str> = seq.iter().filter_map(|v| v.as_str()).collect();
-                    json!(items)
-                }
-                Some(serde_yaml::Value::Null) | None => serde_json::Value::Null,
-                Some(other) => json!(serde_yaml::to_string(other).unwrap_or_default().trim()),
+fn run_repl(args: &ReplArgs) -> ExitCode {
+    let vault_root = &args.vault_root;
+    let mut repl = Repl {
+        notes: note::walk_vault(vault_root),
+        this_ctx: ThisContext::default(),
+        options: args.options,
+        selected: None,
+    };
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!(
+            "Loaded {} notes from {} — :help for commands",
+            repl.notes.len(),
+            vault_root.display()
+        );
+    }
+
+    let mut stdin = io::stdin().lock();
+    let mut line = String::new();
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+        line.clear();
+        match stdin.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::from(1);
             }
         }
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        if matches!(input, ":quit" | ":q") {
+            break;
+        }
+        println!("{}", repl.handle(input));
     }
+
+    ExitCode::SUCCESS
 }
 
 fn print_usage() {
-    eprintln!("Usage: obsidian-base <file.base> [--view <name>] [--paths]");
+    eprintln!("Usage: obsidian-base <file.base> [--view <name>] [--format <fmt>] [--paths]");
+    eprintln!("                         [--inline-fields]");
+    eprintln!("       obsidian-base <file.base> --explain [--note <path>] [--view <name>]");
+    eprintln!(
+        "       obsidian-base <file.base> --watch [--changes] [--view <name>] [--format <fmt>]"
+    );
+    eprintln!("       obsidian-base query [--vault <dir>] [--filter <expr>]... [--sort <prop>]...");
+    eprintln!("                           [--columns <a,b,c>] [--format <fmt>] [--paths]");
+    eprintln!("                           [--inline-fields]");
+    eprintln!("       obsidian-base tasks [--vault <dir>] [--filter <expr>]... [--format <fmt>]");
+    eprintln!("                           [--inline-fields]");
+    eprintln!("       obsidian-base repl [--vault <dir>] [--inline-fields]");
     eprintln!();
-    eprintln!("Resolve an Obsidian Base file against its vault.");
+    eprintln!("Resolve an Obsidian Base file against its vault, run an ad-hoc query with");
+    eprintln!("the Base expression language, or evaluate expressions interactively (repl).");
     eprintln!();
     eprintln!("Options:");
-    eprintln!("  --view <name>  Only resolve the named view");
-    eprintln!("  --paths        Output file paths only (one per line)");
-    eprintln!("  -h, --help     Show this help");
+    eprintln!("  --view <name>   Only resolve the named view");
+    eprintln!("  --format <fmt>  jsonl (default), json, csv, tsv, markdown, paths");
+    eprintln!("  --paths         Output file paths only (same as --format paths)");
+    eprintln!("  --explain       Show how each filter evaluated, per note (text only; not with");
+    eprintln!("                  --watch, --snapshot, --diff or --format)");
+    eprintln!("  --note <path>   Explain a single note (vault-relative path or name)");
+    eprintln!("  --watch         Keep running; re-emit results when the vault changes");
+    eprintln!("  --changes       With --watch, emit only added/removed/changed rows as JSONL");
+    eprintln!("  --snapshot <f>  Also save the resolution to <f> as JSONL (not with --watch)");
+    eprintln!("  --diff <f>      Emit rows added, removed or changed since snapshot <f>, as jsonl");
+    eprintln!("                  or (with --format json) a JSON array");
+    eprintln!("  --inline-fields Let `note.key` fall back to Dataview inline fields (`key:: v`)");
+    eprintln!("                  when the frontmatter has no `key`: in filters, columns and");
+    eprintln!("                  sorts, for every command");
+    eprintln!("  -h, --help      Show this help");
+    eprintln!();
+    eprintln!("Query options:");
+    eprintln!("  --vault <dir>   Vault root (default: nearest .obsidian/ above the cwd)");
+    eprintln!("  --filter <expr> Filter expression; repeat to require all of them");
+    eprintln!("  --sort <prop>   Sort key, with optional :asc or :desc; repeatable");
+    eprintln!("  --columns <..>  Comma-separated columns to output");
+    eprintln!();
+    eprintln!("Tasks: one row per `- [ ]` checkbox in the vault. Filters see the task as");
+    eprintln!("`task` (status, text, line, section, completed, closed, nested, due,");
+    eprintln!("scheduled, start, completion, created) next to the note's own properties:");
+    eprintln!("  obsidian-base tasks --filter '!task.closed && task.due < today()'");
 }
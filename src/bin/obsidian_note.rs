@@ -0,0 +1,262 @@
+use chrono::NaiveDate;
+use forge_obsidian::note::Frontmatter;
+use forge_obsidian::projects;
+use forge_obsidian::vault::{self, LinkFormat, LinkIndex};
+use serde_yaml::Value;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+enum Command {
+    Prop(PropArgs),
+    WorkLog(WorkLogArgs),
+}
+
+enum PropEdit {
+    Set { key: String, value: Value },
+    Remove { key: String },
+    Rename { from: String, to: String },
+    Append { key: String, value: Value },
+}
+
+struct PropArgs {
+    note: PathBuf,
+    edit: PropEdit,
+}
+
+struct WorkLogArgs {
+    note: PathBuf,
+    entry: String,
+    date: NaiveDate,
+    daily: bool,
+}
+
+fn parse_args() -> Result<Command, ExitCode> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    // Past `--` a `-h` is an argument (a work-log entry), not a request for help
+    if args
+        .iter()
+        .take_while(|a| *a != "--")
+        .any(|a| a == "--help" || a == "-h")
+    {
+        print_usage();
+        return Err(ExitCode::SUCCESS);
+    }
+    if args.first().map(String::as_str) == Some("worklog") {
+        return parse_worklog_args(&args[1..]).map(Command::WorkLog);
+    }
+    parse_prop_args(&args).map(Command::Prop)
+}
+
+fn parse_prop_args(args: &[String]) -> Result<PropArgs, ExitCode> {
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    let edit = match words.as_slice() {
+        ["prop", "set", _, key, value] => PropEdit::Set {
+            key: (*key).to_owned(),
+            value: parse_value(value),
+        },
+        ["prop", "remove", _, key] => PropEdit::Remove {
+            key: (*key).to_owned(),
+        },
+        ["prop", "rename", _, from, to] => PropEdit::Rename {
+            from: (*from).to_owned(),
+            to: (*to).to_owned(),
+        },
+        ["prop", "append", _, key, value] => PropEdit::Append {
+            key: (*key).to_owned(),
+            value: parse_value(value),
+        },
+        _ => {
+            print_usage();
+            return Err(ExitCode::from(1));
+        }
+    };
+    Ok(PropArgs {
+        note: PathBuf::from(&args[2]),
+        edit,
+    })
+}
+
+fn parse_worklog_args(args: &[String]) -> Result<WorkLogArgs, ExitCode> {
+    let mut positional = Vec::new();
+    let mut date = None;
+    let mut daily = false;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            // Everything after `--` is positional, so entries may start with `-`
+            "--" => {
+                positional.extend(args[i + 1..].iter().cloned());
+                break;
+            }
+            "--date" => {
+                i += 1;
+                let value = args.get(i).map_or("", String::as_str);
+                date = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                    eprintln!("Error: --date needs YYYY-MM-DD, got `{value}`");
+                    ExitCode::from(1)
+                })?);
+            }
+            "--daily" => daily = true,
+            arg if !arg.starts_with('-') => positional.push(arg.to_owned()),
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    let [note, entry] = <[String; 2]>::try_from(positional).map_err(|_| {
+        eprintln!("Error: worklog needs <note.md> and <entry>");
+        print_usage();
+        ExitCode::from(1)
+    })?;
+
+    Ok(WorkLogArgs {
+        note: PathBuf::from(note),
+        entry,
+        date: date.unwrap_or_else(|| chrono::Local::now().date_naive()),
+        daily,
+    })
+}
+
+/// Interpret a command-line value: a plain number or boolean, or a `[a, b]` flow list of
+/// scalars, keeps its type; everything else is the string as typed.
+///
+/// Text is never read as a mapping or cut at `#`, so `Meeting: Q3` and `see #3` stay whole.
+fn parse_value(raw: &str) -> Value {
+    let text = || Value::String(raw.to_owned());
+    if raw.starts_with("[[") {
+        return text();
+    }
+    if raw.starts_with('[') && raw.ends_with(']') {
+        return match serde_yaml::from_str(raw) {
+            Ok(Value::Sequence(items)) if items.iter().all(is_scalar) => Value::Sequence(items),
+            _ => text(),
+        };
+    }
+    if raw.contains(|c: char| c.is_whitespace() || c == '#') {
+        return text();
+    }
+    match serde_yaml::from_str(raw) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) => value,
+        _ => text(),
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::String(_) | Value::Number(_) | Value::Bool(_) | Value::Null
+    )
+}
+
+fn main() -> ExitCode {
+    let (note, result) = match parse_args() {
+        Ok(Command::Prop(args)) => (args.note.clone(), run_prop(&args)),
+        Ok(Command::WorkLog(args)) => (args.note.clone(), run_worklog(&args)),
+        Err(code) => return code,
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}: {e}", note.display());
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run_prop(args: &PropArgs) -> Result<(), String> {
+    let content = fs::read_to_string(&args.note).map_err(|e| e.to_string())?;
+    let mut frontmatter = Frontmatter::parse(&content)?;
+    match &args.edit {
+        PropEdit::Set { key, value } => frontmatter.set(key, value),
+        PropEdit::Remove { key } => {
+            frontmatter.remove(key);
+        }
+        PropEdit::Rename { from, to } => {
+            if !frontmatter.rename(from, to)? {
+                return Err(format!("no property `{from}`"));
+            }
+        }
+        PropEdit::Append { key, value } => {
+            frontmatter.append(key, value);
+        }
+    }
+
+    let updated = frontmatter.render();
+    if updated != content {
+        vault::write_atomic(&args.note, &updated).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn run_worklog(args: &WorkLogArgs) -> Result<(), String> {
+    let content = fs::read_to_string(&args.note).map_err(|e| e.to_string())?;
+    let date = args.date.format("%Y-%m-%d").to_string();
+    let updated = projects::append_work_log(&content, &date, &args.entry);
+    vault::write_atomic(&args.note, &updated).map_err(|e| e.to_string())?;
+
+    if args.daily {
+        append_daily(args)?;
+    }
+    Ok(())
+}
+
+/// Add `- [[Note]]: entry` to the end of the day's daily note, creating it if needed.
+fn append_daily(args: &WorkLogArgs) -> Result<(), String> {
+    let note = fs::canonicalize(&args.note).map_err(|e| e.to_string())?;
+    let vault_root = vault::find_vault(&note.to_string_lossy())
+        .ok_or("--daily needs the note to be inside a vault (no .obsidian/ above it)")?;
+    let note_rel = note
+        .strip_prefix(&vault_root)
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .into_owned();
+    let daily_rel = vault::daily_note_path(&vault_root, args.date);
+    let link = LinkIndex::build(&vault_root).link_text(
+        &note_rel,
+        &daily_rel,
+        LinkFormat::from_vault(&vault_root),
+    );
+
+    let daily = vault_root.join(&daily_rel);
+    // A daily note that exists but cannot be read must not be replaced by one line
+    let mut content = match fs::read_to_string(&daily) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("{daily_rel}: {e}")),
+    };
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    let content = format!("{content}- [[{link}]]: {}\n", args.entry);
+    fs::create_dir_all(daily.parent().unwrap_or(Path::new(""))).map_err(|e| e.to_string())?;
+    vault::write_atomic(&daily, &content).map_err(|e| format!("{daily_rel}: {e}"))
+}
+
+fn print_usage() {
+    eprintln!("Usage: obsidian-note prop set <note.md> <key> <value>");
+    eprintln!("       obsidian-note prop remove <note.md> <key>");
+    eprintln!("       obsidian-note prop rename <note.md> <old-key> <new-key>");
+    eprintln!("       obsidian-note prop append <note.md> <key> <value>");
+    eprintln!("       obsidian-note worklog [--date YYYY-MM-DD] [--daily] [--] <note.md> <entry>");
+    eprintln!();
+    eprintln!("Edit a note's frontmatter without a running Obsidian. Only the touched");
+    eprintln!("property changes; key order, comments, quoting and the body are preserved.");
+    eprintln!();
+    eprintln!("Numbers, `true`/`false` and `[a, b]` lists keep their types; `[[Wikilinks]]`");
+    eprintln!("and all other text, colons and `#` included, are strings. Keys are top-level, so");
+    eprintln!("`project.status` is a flat dotted key.");
+    eprintln!();
+    eprintln!("worklog adds `- <entry>` under `### [[date]]` in the note's `## Work log`");
+    eprintln!("(today by default), creating the section or date heading in chronological");
+    eprintln!("order. --daily also adds `- [[Note]]: <entry>` to that day's daily note,");
+    eprintln!("located via the Daily notes settings. Put `--` before an entry that starts");
+    eprintln!("with `-`.");
+}
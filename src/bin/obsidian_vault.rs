@@ -0,0 +1,506 @@
+use forge_obsidian::lint;
+use forge_obsidian::note::{self, NoteContext};
+use forge_obsidian::projects;
+use forge_obsidian::schema::{self, Schema};
+use forge_obsidian::vault;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+enum Command {
+    RenameProp(RenamePropArgs),
+    Move(MoveArgs),
+    Check(CheckArgs),
+    Lint(LintArgs),
+    Projects(PathBuf),
+}
+
+struct RenamePropArgs {
+    vault_root: PathBuf,
+    from: String,
+    to: String,
+    filters: Vec<String>,
+    dry_run: bool,
+}
+
+struct MoveArgs {
+    vault_root: PathBuf,
+    from: String,
+    to: String,
+    dry_run: bool,
+}
+
+struct CheckArgs {
+    schema_path: PathBuf,
+    paths: Vec<String>,
+    json: bool,
+}
+
+struct LintArgs {
+    vault_dir: Option<String>,
+    paths: Vec<String>,
+    fix: bool,
+    json: bool,
+}
+
+fn parse_args() -> Result<Command, ExitCode> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("rename-prop") => parse_rename_prop_args(&args[1..]).map(Command::RenameProp),
+        Some("mv") => parse_move_args(&args[1..]).map(Command::Move),
+        Some("check") => parse_check_args(&args[1..]).map(Command::Check),
+        Some("lint") => parse_lint_args(&args[1..]).map(Command::Lint),
+        Some("projects") => parse_projects_args(&args[1..]).map(Command::Projects),
+        Some("--help" | "-h") => {
+            print_usage();
+            Err(ExitCode::SUCCESS)
+        }
+        _ => {
+            print_usage();
+            Err(ExitCode::from(1))
+        }
+    }
+}
+
+fn parse_rename_prop_args(args: &[String]) -> Result<RenamePropArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut keys = Vec::new();
+    let mut filters = Vec::new();
+    let mut dry_run = false;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--filter" => {
+                i += 1;
+                filters.extend(args.get(i).cloned());
+            }
+            "--dry-run" | "-n" => dry_run = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            arg if !arg.starts_with('-') => keys.push(arg.to_owned()),
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    let [from, to] = <[String; 2]>::try_from(keys).map_err(|_| {
+        eprintln!("Error: rename-prop needs <old-key> and <new-key>");
+        print_usage();
+        ExitCode::from(1)
+    })?;
+
+    Ok(RenamePropArgs {
+        vault_root: vault_root_arg(vault_dir)?,
+        from,
+        to,
+        filters,
+        dry_run,
+    })
+}
+
+fn parse_move_args(args: &[String]) -> Result<MoveArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut paths = Vec::new();
+    let mut dry_run = false;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--dry-run" | "-n" => dry_run = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            arg if !arg.starts_with('-') => paths.push(arg.to_owned()),
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    let [from, to] = <[String; 2]>::try_from(paths).map_err(|_| {
+        eprintln!("Error: mv needs <from> and <to>");
+        print_usage();
+        ExitCode::from(1)
+    })?;
+    let vault_root = vault_root_arg(vault_dir)?;
+
+    Ok(MoveArgs {
+        from: vault_relative(&vault_root, &from),
+        to: vault_relative(&vault_root, &to),
+        vault_root,
+        dry_run,
+    })
+}
+
+fn parse_check_args(args: &[String]) -> Result<CheckArgs, ExitCode> {
+    let mut schema_path = None;
+    let mut paths = Vec::new();
+    let mut json = false;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--schema" => {
+                i += 1;
+                schema_path = args.get(i).map(PathBuf::from);
+            }
+            "--format" => {
+                i += 1;
+                json = match args.get(i).map(String::as_str) {
+                    Some("json") => true,
+                    Some("human") => false,
+                    other => {
+                        eprintln!(
+                            "Unknown format: {} (expected human or json)",
+                            other.unwrap_or("")
+                        );
+                        return Err(ExitCode::from(1));
+                    }
+                };
+            }
+            "--json" => json = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            arg if !arg.starts_with('-') => paths.push(arg.to_owned()),
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    let Some(schema_path) = schema_path else {
+        eprintln!("Error: check needs --schema <file.mdschema>");
+        print_usage();
+        return Err(ExitCode::from(1));
+    };
+    if paths.is_empty() {
+        eprintln!("Error: check needs at least one file, folder or glob");
+        print_usage();
+        return Err(ExitCode::from(1));
+    }
+
+    Ok(CheckArgs {
+        schema_path,
+        paths,
+        json,
+    })
+}
+
+fn parse_lint_args(args: &[String]) -> Result<LintArgs, ExitCode> {
+    let mut vault_dir = None;
+    let mut paths = Vec::new();
+    let mut fix = false;
+    let mut json = false;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--format" => {
+                i += 1;
+                json = match args.get(i).map(String::as_str) {
+                    Some("json") => true,
+                    Some("human") => false,
+                    other => {
+                        eprintln!(
+                            "Unknown format: {} (expected human or json)",
+                            other.unwrap_or("")
+                        );
+                        return Err(ExitCode::from(1));
+                    }
+                };
+            }
+            "--json" => json = true,
+            "--fix" => fix = true,
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            arg if !arg.starts_with('-') => paths.push(arg.to_owned()),
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    Ok(LintArgs {
+        vault_dir,
+        paths,
+        fix,
+        json,
+    })
+}
+
+fn parse_projects_args(args: &[String]) -> Result<PathBuf, ExitCode> {
+    let mut vault_dir = None;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "--vault" => {
+                i += 1;
+                vault_dir = args.get(i).cloned();
+            }
+            "--help" | "-h" => {
+                print_usage();
+                return Err(ExitCode::SUCCESS);
+            }
+            other => {
+                eprintln!("Unknown option: {other}");
+                print_usage();
+                return Err(ExitCode::from(1));
+            }
+        }
+        i += 1;
+    }
+
+    vault_root_arg(vault_dir)
+}
+
+/// Paths are vault-relative; absolute paths inside the vault are accepted too.
+fn vault_relative(vault_root: &Path, path: &str) -> String {
+    let path = Path::new(path);
+    path.strip_prefix(vault_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn vault_root_arg(vault_dir: Option<String>) -> Result<PathBuf, ExitCode> {
+    match vault_dir {
+        Some(dir) => std::fs::canonicalize(&dir).map_err(|e| {
+            eprintln!("Cannot resolve {dir}: {e}");
+            ExitCode::from(1)
+        }),
+        None => vault::find_vault_from_cwd().ok_or_else(|| {
+            eprintln!("Cannot find vault root (pass --vault or run inside a vault)");
+            ExitCode::from(1)
+        }),
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_args() {
+        Ok(Command::RenameProp(args)) => run_rename_prop(&args),
+        Ok(Command::Move(args)) => run_move(&args),
+        Ok(Command::Check(args)) => run_check(&args),
+        Ok(Command::Lint(args)) => run_lint(&args),
+        Ok(Command::Projects(vault_root)) => run_projects(&vault_root),
+        Err(code) => code,
+    }
+}
+
+fn run_rename_prop(args: &RenamePropArgs) -> ExitCode {
+    let edits = match vault::rename_property(&args.vault_root, &args.from, &args.to, &args.filters)
+    {
+        Ok(edits) => edits,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::from(1);
+        }
+    };
+
+    if args.dry_run {
+        for edit in &edits {
+            print!("{}", edit.diff());
+        }
+    } else {
+        if let Err(e) = vault::apply(&edits) {
+            eprintln!("Error: {e}");
+            return ExitCode::from(1);
+        }
+        for edit in &edits {
+            println!("{}", edit.rel_path);
+        }
+    }
+    eprintln!(
+        "{} `{}` → `{}` in {} note(s)",
+        if args.dry_run {
+            "Would rename"
+        } else {
+            "Renamed"
+        },
+        args.from,
+        args.to,
+        edits.len()
+    );
+    ExitCode::SUCCESS
+}
+
+fn run_move(args: &MoveArgs) -> ExitCode {
+    let plan = match vault::rename(&args.vault_root, &args.from, &args.to) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::from(1);
+        }
+    };
+    print!("{}", plan.report());
+    if !args.dry_run {
+        if let Err(e) = plan.apply() {
+            eprintln!("Error: {e}");
+            return ExitCode::from(1);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Exits 1 when any file has violations, so `check` can gate scripts.
+fn run_check(args: &CheckArgs) -> ExitCode {
+    let checked = Schema::parse_file(&args.schema_path)
+        .and_then(|s| Ok((s, schema::expand_paths(&args.paths)?)));
+    let (schema, paths) = match checked {
+        Ok(checked) => checked,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::from(1);
+        }
+    };
+
+    let reports = schema::check(&schema, &paths);
+    if args.json {
+        print!("{}", schema::render_json(&reports));
+    } else {
+        print!("{}", schema::render_human(&reports));
+    }
+    if reports.iter().all(|r| r.violations.is_empty()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// Notes named on the command line, or the whole vault when none are.
+fn lint_targets(args: &LintArgs) -> Result<Vec<NoteContext>, ExitCode> {
+    if args.paths.is_empty() {
+        return Ok(note::walk_vault(&vault_root_arg(args.vault_dir.clone())?));
+    }
+    let paths = schema::expand_paths(&args.paths).map_err(|e| {
+        eprintln!("Error: {e}");
+        ExitCode::from(1)
+    })?;
+    Ok(paths
+        .iter()
+        .filter_map(|path| NoteContext::from_file(Path::new(""), path))
+        .collect())
+}
+
+/// With `--fix`, rewrites fixable notes first and reports what is left.
+/// Exits 1 while any finding remains.
+fn run_lint(args: &LintArgs) -> ExitCode {
+    let mut notes = match lint_targets(args) {
+        Ok(notes) => notes,
+        Err(code) => return code,
+    };
+
+    if args.fix {
+        for note in &notes {
+            if !lint::lint_note(note).iter().any(|f| f.fixable) {
+                continue;
+            }
+            let fixed = fs::read_to_string(&note.path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| lint::fix(&content))
+                .and_then(|fixed| {
+                    vault::write_atomic(&note.path, &fixed).map_err(|e| e.to_string())
+                });
+            match fixed {
+                Ok(()) => eprintln!("Fixed {}", note.rel_path),
+                Err(e) => eprintln!("{}: {e}", note.rel_path),
+            }
+        }
+        notes = notes
+            .iter()
+            .filter_map(|note| NoteContext::from_file(Path::new(""), &note.path))
+            .collect();
+    }
+
+    let reports = lint::lint_notes(&notes);
+    if args.json {
+        print!("{}", lint::render_json(&reports));
+    } else {
+        print!("{}", lint::render_human(&reports));
+    }
+    if reports.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// JSONL violations on stdout; exits 1 when any project breaks a rule.
+fn run_projects(vault_root: &Path) -> ExitCode {
+    let violations = projects::check(&note::walk_vault(vault_root));
+    print!("{}", projects::render_jsonl(&violations));
+    if violations.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: obsidian-vault rename-prop <old-key> <new-key> [--vault <dir>]");
+    eprintln!("                                  [--filter <expr>]... [--dry-run]");
+    eprintln!("       obsidian-vault mv <from> <to> [--vault <dir>] [--dry-run]");
+    eprintln!(
+        "       obsidian-vault check <file|dir|glob>... --schema <.mdschema> [--format json]"
+    );
+    eprintln!("       obsidian-vault lint [<file|dir|glob>...] [--vault <dir>] [--fix]");
+    eprintln!("                           [--format json]");
+    eprintln!("       obsidian-vault projects [--vault <dir>]");
+    eprintln!();
+    eprintln!("Vault-wide maintenance without a running Obsidian. Edits preserve each");
+    eprintln!("note's formatting and are written atomically.");
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("  rename-prop     Rename a frontmatter key in every note that has it");
+    eprintln!("  mv              Move or rename a note and rewrite every wikilink and Markdown");
+    eprintln!("                  link to it (not those in code), in the vault's newLinkFormat;");
+    eprintln!("                  paths are vault-relative");
+    eprintln!("  check           Validate notes against a .mdschema (frontmatter fields,");
+    eprintln!("                  required sections, heading order); exits 1 on violations");
+    eprintln!("  lint            Enforce flat frontmatter: no nested objects or lists of");
+    eprintln!("                  objects, values containing `:` quoted; exits 1 on findings");
+    eprintln!("  projects        Check projects against the ProjectManagement rules (folder");
+    eprintln!("                  note, status/priority enums, no open tasks once completed,");
+    eprintln!("                  `updated:` date, `### [[YYYY-MM-DD]]` work log); JSONL output");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --vault <dir>   Vault root (default: nearest .obsidian/ above the cwd)");
+    eprintln!("  --filter <expr> Only touch notes matching this Base expression; repeatable");
+    eprintln!("  -n, --dry-run   Show the changes (diffs, or the links mv would rewrite)");
+    eprintln!("                  without writing");
+    eprintln!("  --fix           lint: quote colon values and flatten `a: {{b: x}}` to `a.b: x`");
+}
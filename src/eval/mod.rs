@@ -1,8 +1,15 @@
 #[cfg(test)]
 mod tests;
 
-use crate::note::NoteContext;
+use crate::note::{NoteContext, Task};
+use regex::Regex;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::path::Path;
+use std::rc::Rc;
+use std::sync::{LazyLock, Mutex, PoisonError};
 
 // ─── Values ─────────────────────────────────────────────────
 
@@ -10,18 +17,36 @@ use std::path::Path;
 #[derive(Debug, Clone)]
 pub enum Val {
     Str(String),
+    Num(f64),
     Bool(bool),
-    List(Vec<String>),
+    List(Vec<Val>),
+    /// A YAML mapping: `project: {status: active}`.
+    Object(BTreeMap<String, Val>),
+    /// A vault file by vault-relative path: bare `file`, `file("Notes/Idea.md")`.
+    File(String),
+    /// A wikilink: `"[[Obsidian|display]]"` in frontmatter, `link("Obsidian")`, `file.asLink()`.
+    Link {
+        target: String,
+        display: Option<String>,
+    },
+    /// A regular expression literal, compiled at parse time: `/^\d{4}-/i`.
+    Regex {
+        source: String,
+        regex: Regex,
+    },
     Null,
 }
 
 impl Val {
     pub fn as_str(&self) -> &str {
         match self {
-            Val::Str(s) => s,
+            Val::Str(s)
+            | Val::File(s)
+            | Val::Link { target: s, .. }
+            | Val::Regex { source: s, .. } => s,
             Val::Bool(true) => "true",
             Val::Bool(false) => "false",
-            Val::Null | Val::List(_) => "",
+            Val::Num(_) | Val::Null | Val::List(_) | Val::Object(_) => "",
         }
     }
 
@@ -29,17 +54,50 @@ impl Val {
         match self {
             Val::Bool(b) => *b,
             Val::Str(s) => !s.is_empty(),
+            Val::Num(n) => *n != 0.0,
             Val::List(l) => !l.is_empty(),
+            Val::Object(o) => !o.is_empty(),
+            Val::File(_) | Val::Link { .. } | Val::Regex { .. } => true,
             Val::Null => false,
         }
     }
 
     fn to_string_val(&self) -> String {
         match self {
-            Val::Str(s) => s.clone(),
+            Val::Str(s) | Val::File(s) | Val::Regex { source: s, .. } => s.clone(),
+            Val::Num(n) => n.to_string(),
             Val::Bool(b) => b.to_string(),
             Val::Null => String::new(),
-            Val::List(l) => l.join(", "),
+            Val::List(l) => l
+                .iter()
+                .map(Val::to_string_val)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Val::Object(_) => self.to_string(),
+            Val::Link { target, display } => wikilink(target, display.as_deref()),
+        }
+    }
+}
+
+impl fmt::Display for Val {
+    /// Literal-like rendering: strings quoted, lists and objects bracketed, `null` for missing values.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Val::Str(s) => write!(f, "{s:?}"),
+            Val::Num(n) => write!(f, "{n}"),
+            Val::Bool(b) => write!(f, "{b}"),
+            Val::List(l) => {
+                let items: Vec<String> = l.iter().map(ToString::to_string).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Val::Object(o) => {
+                let entries: Vec<String> = o.iter().map(|(k, v)| format!("{k:?}: {v}")).collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Val::File(path) => write!(f, "file({path:?})"),
+            Val::Link { .. } => f.write_str(&self.to_string_val()),
+            Val::Regex { source, .. } => f.write_str(source),
+            Val::Null => f.write_str("null"),
         }
     }
 }
@@ -50,96 +108,267 @@ impl Val {
 enum Token {
     Ident(String),
     Str(String),
-    Int(i64),
+    Num(f64),
+    Bool(bool),
+    Null,
+    /// `/pattern/flags`
+    Regex {
+        pattern: String,
+        flags: String,
+    },
     Dot,
     LParen,
     RParen,
+    LBracket,
+    RBracket,
     Comma,
     Not,
     Eq,
     Neq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+}
+
+/// A token with the char range it covers in the source.
+#[derive(Debug, Clone, PartialEq)]
+struct Spanned {
+    token: Token,
+    start: usize,
+    end: usize,
 }
 
+#[cfg(test)]
 fn tokenize(input: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let chars: Vec<char> = input.chars().collect();
-    let mut i = 0;
-
-    while i < chars.len() {
-        match chars[i] {
-            ' ' | '\t' => i += 1,
-            '.' => {
-                tokens.push(Token::Dot);
-                i += 1;
-            }
-            '(' => {
-                tokens.push(Token::LParen);
-                i += 1;
-            }
-            ')' => {
-                tokens.push(Token::RParen);
-                i += 1;
-            }
-            ',' => {
-                tokens.push(Token::Comma);
-                i += 1;
-            }
-            '!' if i + 1 < chars.len() && chars[i + 1] == '=' => {
-                tokens.push(Token::Neq);
-                i += 2;
-            }
-            '!' => {
-                tokens.push(Token::Not);
-                i += 1;
-            }
-            '=' if i + 1 < chars.len() && chars[i + 1] == '=' => {
-                tokens.push(Token::Eq);
-                i += 2;
-            }
-            '"' | '\'' => {
-                let quote = chars[i];
-                i += 1;
-                let start = i;
-                while i < chars.len() && chars[i] != quote {
-                    i += 1;
-                }
-                let s: String = chars[start..i].iter().collect();
-                tokens.push(Token::Str(s));
-                if i < chars.len() {
-                    i += 1; // closing quote
+    lex(input)
+        .map(|tokens| tokens.into_iter().map(|t| t.token).collect())
+        .unwrap_or_default()
+}
+
+/// Split an expression into spanned tokens.
+///
+/// A `/` is always division (or opens a regex literal), as in Obsidian: `price/qty`
+/// divides, and path-like text such as `"type/project"` must be quoted.
+fn lex(input: &str) -> Result<Vec<Spanned>, ParseError> {
+    let mut lexer = Lexer {
+        chars: input.chars().collect(),
+        pos: 0,
+        tokens: Vec::new(),
+    };
+    while let Some(c) = lexer.peek() {
+        if c.is_whitespace() {
+            lexer.pos += 1;
+            continue;
+        }
+        let start = lexer.pos;
+        let token = match c {
+            '"' | '\'' => lexer.string(c)?,
+            // A slash where an operand is expected starts a regex literal
+            '/' if !lexer.follows_operand() => lexer.regex()?,
+            c if c.is_ascii_digit() => lexer.number(),
+            c if c.is_alphabetic() || c == '_' || c == '#' => lexer.word(),
+            _ => lexer.operator()?,
+        };
+        lexer.tokens.push(Spanned {
+            token,
+            start,
+            end: lexer.pos,
+        });
+    }
+    Ok(lexer.tokens)
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    tokens: Vec<Spanned>,
+}
+
+impl Lexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matched = self.peek() == Some(c);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    /// Whether the last token ends an operand, so a following `/` cannot start a regex.
+    fn follows_operand(&self) -> bool {
+        matches!(
+            self.tokens.last().map(|t| &t.token),
+            Some(
+                Token::Ident(_)
+                    | Token::Str(_)
+                    | Token::Num(_)
+                    | Token::Bool(_)
+                    | Token::Null
+                    | Token::Regex { .. }
+                    | Token::RParen
+                    | Token::RBracket
+            )
+        )
+    }
+
+    /// Scan a quoted string. Unknown escapes are kept verbatim so `"\d+"` still
+    /// reaches the regex engine intact.
+    fn string(&mut self, quote: char) -> Result<Token, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == quote {
+                return Ok(Token::Str(s));
+            }
+            if c != '\\' {
+                s.push(c);
+                continue;
+            }
+            let Some(escaped) = self.peek() else { break };
+            self.pos += 1;
+            match escaped {
+                'n' => s.push('\n'),
+                't' => s.push('\t'),
+                'r' => s.push('\r'),
+                '\\' | '"' | '\'' => s.push(escaped),
+                other => {
+                    s.push('\\');
+                    s.push(other);
                 }
             }
-            c if c.is_ascii_digit()
-                || (c == '-' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) =>
-            {
-                let start = i;
-                if c == '-' {
-                    i += 1;
+        }
+        Err(ParseError::new("unterminated string", start))
+    }
+
+    /// Scan a `/pattern/flags` literal; `\/` is a literal slash.
+    fn regex(&mut self) -> Result<Token, ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut pattern = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(ParseError::new("unterminated regex", start)),
+                Some('/') => break,
+                Some('\\') if self.peek_at(1) == Some('/') => {
+                    pattern.push('/');
+                    self.pos += 2;
                 }
-                while i < chars.len() && chars[i].is_ascii_digit() {
-                    i += 1;
+                Some('\\') => {
+                    pattern.push('\\');
+                    self.pos += 1;
+                    if let Some(c) = self.peek() {
+                        pattern.push(c);
+                        self.pos += 1;
+                    }
                 }
-                let s: String = chars[start..i].iter().collect();
-                tokens.push(Token::Int(s.parse().unwrap_or(0)));
-            }
-            c if c.is_alphanumeric() || c == '_' || c == '/' || c == '#' => {
-                let start = i;
-                while i < chars.len()
-                    && (chars[i].is_alphanumeric()
-                        || chars[i] == '_'
-                        || chars[i] == '/'
-                        || chars[i] == '#')
-                {
-                    i += 1;
+                Some(c) => {
+                    pattern.push(c);
+                    self.pos += 1;
                 }
-                let s: String = chars[start..i].iter().collect();
-                tokens.push(Token::Ident(s));
             }
-            _ => i += 1, // skip unknown
         }
+        self.pos += 1; // closing slash
+        let mut flags = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            flags.push(c);
+            self.pos += 1;
+        }
+        Ok(Token::Regex { pattern, flags })
+    }
+
+    /// Scan an integer or decimal literal with an optional exponent: `3`, `1.5`, `2e-3`.
+    fn number(&mut self) -> Token {
+        let start = self.pos;
+        self.skip_digits();
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let sign = usize::from(matches!(self.peek_at(1), Some('+' | '-')));
+            if self.peek_at(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1 + sign;
+                self.skip_digits();
+            }
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        Token::Num(text.parse().unwrap_or(0.0))
     }
 
-    tokens
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Scan an identifier or keyword literal (`true`, `false`, `null`).
+    fn word(&mut self) -> Token {
+        let start = self.pos;
+        self.pos += 1;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match text.as_str() {
+            "true" => Token::Bool(true),
+            "false" => Token::Bool(false),
+            "null" => Token::Null,
+            _ => Token::Ident(text),
+        }
+    }
+
+    fn operator(&mut self) -> Result<Token, ParseError> {
+        let start = self.pos;
+        let c = self.peek().unwrap_or_default();
+        self.pos += 1;
+        let token = match c {
+            '.' => Token::Dot,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '!' if self.eat('=') => Token::Neq,
+            '!' => Token::Not,
+            '<' if self.eat('=') => Token::Le,
+            '<' => Token::Lt,
+            '>' if self.eat('=') => Token::Ge,
+            '>' => Token::Gt,
+            '=' if self.eat('=') => Token::Eq,
+            '&' if self.eat('&') => Token::And,
+            '|' if self.eat('|') => Token::Or,
+            '=' => return Err(ParseError::new("unexpected `=`, use `==`", start)),
+            other => {
+                return Err(ParseError::new(
+                    &format!("unexpected character `{other}`"),
+                    start,
+                ))
+            }
+        };
+        Ok(token)
+    }
 }
 
 // ─── AST ────────────────────────────────────────────────────
@@ -150,6 +379,13 @@ enum Expr {
     Literal(Val),
     /// Property chain: file.name, property.tags, this.file.name
     Property(Vec<String>),
+    /// Field access on a computed value: expr.length
+    Field { receiver: Box<Expr>, name: String },
+    /// Bracket indexing: `list[0]`, `obj["key"]`
+    Index {
+        receiver: Box<Expr>,
+        index: Box<Expr>,
+    },
     /// Method call: expr.method(args...)
     MethodCall {
         receiver: Box<Expr>,
@@ -162,7 +398,7 @@ enum Expr {
         receiver: Option<Box<Expr>>,
         args: Vec<Expr>,
     },
-    /// Binary comparison: a != b, a == b
+    /// Binary operation: a == b, a && b, a + b
     BinOp {
         op: BinOperator,
         left: Box<Expr>,
@@ -170,32 +406,184 @@ enum Expr {
     },
     /// Prefix negation: !expr
     Not(Box<Expr>),
+    /// Arithmetic negation: -expr
+    Neg(Box<Expr>),
 }
 
 #[derive(Debug, Clone, Copy)]
 enum BinOperator {
     Eq,
     Neq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl BinOperator {
+    fn from_token(token: &Token) -> Option<Self> {
+        Some(match token {
+            Token::Eq => Self::Eq,
+            Token::Neq => Self::Neq,
+            Token::Lt => Self::Lt,
+            Token::Le => Self::Le,
+            Token::Gt => Self::Gt,
+            Token::Ge => Self::Ge,
+            Token::And => Self::And,
+            Token::Or => Self::Or,
+            Token::Plus => Self::Add,
+            Token::Minus => Self::Sub,
+            Token::Star => Self::Mul,
+            Token::Slash => Self::Div,
+            Token::Percent => Self::Mod,
+            _ => return None,
+        })
+    }
+
+    /// Binding strength; higher binds tighter.
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Or => 1,
+            Self::And => 2,
+            Self::Eq | Self::Neq | Self::Lt | Self::Le | Self::Gt | Self::Ge => 3,
+            Self::Add | Self::Sub => 4,
+            Self::Mul | Self::Div | Self::Mod => 5,
+        }
+    }
+}
+
+/// Render a sub-expression, parenthesizing binary operations weaker than `min_precedence`.
+fn operand(expr: &Expr, min_precedence: u8) -> String {
+    match expr {
+        Expr::BinOp { op, .. } if op.precedence() < min_precedence => format!("({expr})"),
+        _ => expr.to_string(),
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Render the expression back to source form (used by explain output).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(v) => write!(f, "{v}"),
+            Expr::Property(chain) => f.write_str(&chain.join(".")),
+            Expr::Field { receiver, name } => write!(f, "{}.{name}", operand(receiver, u8::MAX)),
+            Expr::Index { receiver, index } => {
+                write!(f, "{}[{index}]", operand(receiver, u8::MAX))
+            }
+            Expr::MethodCall {
+                receiver,
+                method: name,
+                args,
+            }
+            | Expr::FuncCall {
+                name,
+                receiver: Some(receiver),
+                args,
+            } => write!(
+                f,
+                "{}.{name}({})",
+                operand(receiver, u8::MAX),
+                join_args(args)
+            ),
+            Expr::FuncCall {
+                name,
+                receiver: None,
+                args,
+            } => write!(f, "{name}({})", join_args(args)),
+            Expr::BinOp { op, left, right } => write!(
+                f,
+                "{} {op} {}",
+                operand(left, op.precedence()),
+                operand(right, op.precedence() + 1)
+            ),
+            Expr::Not(inner) => write!(f, "!{}", operand(inner, u8::MAX)),
+            Expr::Neg(inner) => write!(f, "-{}", operand(inner, u8::MAX)),
+        }
+    }
+}
+
+impl fmt::Display for BinOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BinOperator::Eq => "==",
+            BinOperator::Neq => "!=",
+            BinOperator::Lt => "<",
+            BinOperator::Le => "<=",
+            BinOperator::Gt => ">",
+            BinOperator::Ge => ">=",
+            BinOperator::And => "&&",
+            BinOperator::Or => "||",
+            BinOperator::Add => "+",
+            BinOperator::Sub => "-",
+            BinOperator::Mul => "*",
+            BinOperator::Div => "/",
+            BinOperator::Mod => "%",
+        })
+    }
+}
+
+fn join_args(args: &[Expr]) -> String {
+    args.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 // ─── Parser ─────────────────────────────────────────────────
 
 struct Parser {
-    tokens: Vec<Token>,
+    tokens: Vec<Spanned>,
+    /// Char length of the source, used as the offset of end-of-input errors.
+    input_len: usize,
+    /// First error encountered; parsing continues leniently past it.
+    error: Option<ParseError>,
     pos: usize,
 }
 
 impl Parser {
-    fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+    fn from_source(input: &str) -> Self {
+        let (tokens, error) = match lex(input) {
+            Ok(tokens) => (tokens, None),
+            Err(err) => (Vec::new(), Some(err)),
+        };
+        Self {
+            tokens,
+            input_len: input.chars().count(),
+            error,
+            pos: 0,
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.input_len, |t| t.start)
+    }
+
+    /// Record an error at the current token, keeping the first one.
+    fn fail(&mut self, message: &str) {
+        self.fail_at(message, self.offset());
+    }
+
+    fn fail_at(&mut self, message: &str, offset: usize) {
+        if self.error.is_none() {
+            self.error = Some(ParseError::new(message, offset));
+        }
     }
 
     fn peek(&self) -> Option<&Token> {
-        self.tokens.get(self.pos)
+        self.tokens.get(self.pos).map(|t| &t.token)
     }
 
     fn advance(&mut self) -> Option<Token> {
-        let tok = self.tokens.get(self.pos).cloned();
+        let tok = self.peek().cloned();
         self.pos += 1;
         tok
     }
@@ -205,61 +593,96 @@ impl Parser {
             self.advance();
             true
         } else {
+            self.fail(&format!("expected {}", describe_token(expected)));
             false
         }
     }
 
     /// Parse a full expression.
     fn parse_expr(&mut self) -> Option<Expr> {
-        let left = self.parse_unary()?;
+        self.parse_binary(1)
+    }
 
-        // Check for binary operators
-        match self.peek() {
-            Some(Token::Neq) => {
-                self.advance();
-                let right = self.parse_unary()?;
-                Some(Expr::BinOp {
-                    op: BinOperator::Neq,
-                    left: Box::new(left),
-                    right: Box::new(right),
-                })
+    /// Precedence climbing: `||` < `&&` < comparisons < `+ -` < `* / %`, all left-associative.
+    fn parse_binary(&mut self, min_precedence: u8) -> Option<Expr> {
+        let mut left = self.parse_unary()?;
+        while let Some(op) = self.peek().and_then(BinOperator::from_token) {
+            if op.precedence() < min_precedence {
+                break;
             }
-            Some(Token::Eq) => {
-                self.advance();
-                let right = self.parse_unary()?;
-                Some(Expr::BinOp {
-                    op: BinOperator::Eq,
-                    left: Box::new(left),
-                    right: Box::new(right),
-                })
-            }
-            _ => Some(left),
+            self.advance();
+            let right = self.parse_binary(op.precedence() + 1)?;
+            left = Expr::BinOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
         }
+        Some(left)
     }
 
     fn parse_unary(&mut self) -> Option<Expr> {
-        if self.peek() == Some(&Token::Not) {
-            self.advance();
-            let inner = self.parse_primary()?;
-            return Some(Expr::Not(Box::new(inner)));
+        match self.peek() {
+            Some(Token::Not) => {
+                self.advance();
+                let inner = self.parse_unary()?;
+                Some(Expr::Not(Box::new(inner)))
+            }
+            Some(Token::Minus) => {
+                self.advance();
+                match self.parse_unary()? {
+                    Expr::Literal(Val::Num(n)) => Some(Expr::Literal(Val::Num(-n))),
+                    inner => Some(Expr::Neg(Box::new(inner))),
+                }
+            }
+            _ => self.parse_primary(),
         }
-        self.parse_primary()
     }
 
     fn parse_primary(&mut self) -> Option<Expr> {
-        let base = match self.peek()? {
+        let Some(next) = self.peek() else {
+            self.fail("unexpected end of expression");
+            return None;
+        };
+        let base = match next {
             Token::Str(_) => {
                 let Token::Str(s) = self.advance()? else {
                     unreachable!()
                 };
                 Expr::Literal(Val::Str(s))
             }
-            Token::Int(_) => {
-                let Token::Int(n) = self.advance()? else {
+            Token::Num(_) => {
+                let Token::Num(n) = self.advance()? else {
                     unreachable!()
                 };
-                Expr::Literal(Val::Str(n.to_string()))
+                Expr::Literal(Val::Num(n))
+            }
+            Token::Bool(b) => {
+                let b = *b;
+                self.advance();
+                Expr::Literal(Val::Bool(b))
             }
+            Token::Null => {
+                self.advance();
+                Expr::Literal(Val::Null)
+            }
+            Token::LParen => {
+                self.advance();
+                let inner = self.parse_expr()?;
+                self.expect(&Token::RParen);
+                inner
+            }
+            Token::Regex { pattern, flags } => match compile_regex(pattern, flags) {
+                Ok(regex) => {
+                    let source = format!("/{}/{flags}", pattern.replace('/', "\\/"));
+                    self.advance();
+                    Expr::Literal(Val::Regex { source, regex })
+                }
+                Err(message) => {
+                    self.fail(&message);
+                    return None;
+                }
+            },
             Token::Ident(_) => {
                 let Token::Ident(name) = self.advance()? else {
                     unreachable!()
@@ -267,9 +690,7 @@ impl Parser {
 
                 // Check if this is a standalone function call: name(args)
                 if self.peek() == Some(&Token::LParen) {
-                    self.advance(); // consume (
-                    let args = self.parse_arg_list();
-                    self.expect(&Token::RParen);
+                    let args = self.parse_call_args(&name);
                     Expr::FuncCall {
                         name,
                         receiver: None,
@@ -292,9 +713,7 @@ impl Parser {
                         // Check if last part is a function call
                         if self.peek() == Some(&Token::LParen) {
                             let method = chain.pop().unwrap();
-                            self.advance(); // consume (
-                            let args = self.parse_arg_list();
-                            self.expect(&Token::RParen);
+                            let args = self.parse_call_args(&method);
 
                             let receiver = Expr::Property(chain.clone());
 
@@ -320,26 +739,43 @@ impl Parser {
                     Expr::Property(chain)
                 }
             }
-            _ => return None,
+            other => {
+                let message = format!("unexpected {}", describe_token(other));
+                self.fail(&message);
+                return None;
+            }
         };
 
         // Check for chained method calls on the base
         Some(self.parse_chain(base))
     }
 
-    /// Parse chained method calls after a base expression.
+    /// Parse chained method calls, field accesses and indexing after a base expression.
     fn parse_chain(&mut self, mut base: Expr) -> Expr {
-        while self.peek() == Some(&Token::Dot) {
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {}
+                Some(Token::LBracket) => {
+                    self.advance(); // consume [
+                    let Some(index) = self.parse_expr() else {
+                        return base;
+                    };
+                    self.expect(&Token::RBracket);
+                    base = Expr::Index {
+                        receiver: Box::new(base),
+                        index: Box::new(index),
+                    };
+                    continue;
+                }
+                _ => break,
+            }
             self.advance(); // consume .
             if let Some(Token::Ident(_)) = self.peek() {
                 let Token::Ident(part) = self.advance().unwrap() else {
                     unreachable!()
                 };
                 if self.peek() == Some(&Token::LParen) {
-                    self.advance(); // consume (
-                    let args = self.parse_arg_list();
-                    self.expect(&Token::RParen);
-
+                    let args = self.parse_call_args(&part);
                     base = if is_builtin_method(&part) {
                         Expr::MethodCall {
                             receiver: Box::new(base),
@@ -354,15 +790,31 @@ impl Parser {
                         }
                     };
                 } else {
-                    // Property access on an expression — convert to method call on toString
-                    // e.g., `this.file` after a method call — rebuild as property
-                    // This is an edge case; for now, just ignore stray dots
+                    base = Expr::Field {
+                        receiver: Box::new(base),
+                        name: part,
+                    };
                 }
             }
         }
         base
     }
 
+    /// Parse `(args)` after a call name. String patterns passed to `matches()` are
+    /// checked here so strict parsing reports invalid regexes.
+    fn parse_call_args(&mut self, name: &str) -> Vec<Expr> {
+        self.advance(); // consume (
+        let args_offset = self.offset();
+        let args = self.parse_arg_list();
+        self.expect(&Token::RParen);
+        if let ("matches", Some(Expr::Literal(Val::Str(pattern)))) = (name, args.first()) {
+            if let Err(message) = compile_regex(pattern, "") {
+                self.fail_at(&message, args_offset);
+            }
+        }
+        args
+    }
+
     fn parse_arg_list(&mut self) -> Vec<Expr> {
         let mut args = Vec::new();
         if self.peek() == Some(&Token::RParen) {
@@ -381,21 +833,159 @@ impl Parser {
     }
 }
 
+fn describe_token(token: &Token) -> String {
+    match token {
+        Token::Ident(s) => format!("identifier `{s}`"),
+        Token::Str(s) => format!("string {s:?}"),
+        Token::Num(n) => format!("number {n}"),
+        Token::Bool(b) => format!("`{b}`"),
+        Token::Null => "`null`".to_owned(),
+        Token::Regex { pattern, flags } => format!("regex /{pattern}/{flags}"),
+        Token::Dot => "`.`".to_owned(),
+        Token::LParen => "`(`".to_owned(),
+        Token::RParen => "`)`".to_owned(),
+        Token::LBracket => "`[`".to_owned(),
+        Token::RBracket => "`]`".to_owned(),
+        Token::Comma => "`,`".to_owned(),
+        Token::Not => "`!`".to_owned(),
+        Token::Eq => "`==`".to_owned(),
+        Token::Neq => "`!=`".to_owned(),
+        Token::Lt => "`<`".to_owned(),
+        Token::Le => "`<=`".to_owned(),
+        Token::Gt => "`>`".to_owned(),
+        Token::Ge => "`>=`".to_owned(),
+        Token::And => "`&&`".to_owned(),
+        Token::Or => "`||`".to_owned(),
+        Token::Plus => "`+`".to_owned(),
+        Token::Minus => "`-`".to_owned(),
+        Token::Star => "`*`".to_owned(),
+        Token::Slash => "`/`".to_owned(),
+        Token::Percent => "`%`".to_owned(),
+    }
+}
+
+/// Expression syntax error at a char offset in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl ParseError {
+    fn new(message: &str, offset: usize) -> Self {
+        Self {
+            message: message.to_owned(),
+            offset,
+        }
+    }
+
+    /// Render the source with a caret under the error position.
+    pub fn caret(&self, source: &str) -> String {
+        format!("{source}\n{}^ {}", " ".repeat(self.offset), self.message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.offset + 1)
+    }
+}
+
+/// A parsed expression, reusable across notes.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    expr: Expr,
+}
+
+impl Expression {
+    /// Parse strictly: unlike `eval_filter`, trailing or malformed input is an error.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::from_source(source);
+        let expr = parser.parse_expr();
+        if parser.error.is_none() && parser.peek().is_some() {
+            let message = format!("unexpected {}", describe_token(parser.peek().unwrap()));
+            parser.fail(&message);
+        }
+        match (expr, parser.error) {
+            (Some(expr), None) => Ok(Self { expr }),
+            (_, Some(err)) => Err(err),
+            (None, None) => Err(ParseError::new("empty expression", 0)),
+        }
+    }
+
+    pub fn eval(&self, note: &NoteContext, ctx: &EvalContext) -> Val {
+        eval_expr(&self.expr, note, ctx)
+    }
+
+    pub fn matches(&self, note: &NoteContext, ctx: &EvalContext) -> bool {
+        self.eval(note, ctx).as_bool()
+    }
+
+    /// Evaluate with extra top-level names in scope, such as `task` in `obsidian-base tasks`.
+    /// Bindings shadow frontmatter properties of the same name.
+    pub fn eval_with(
+        &self,
+        note: &NoteContext,
+        ctx: &EvalContext,
+        bindings: Vec<(String, Val)>,
+    ) -> Val {
+        let mut scope = Scope::new(note, ctx);
+        scope.locals = bindings;
+        scope.eval(&self.expr)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expr.fmt(f)
+    }
+}
+
 fn is_builtin_method(name: &str) -> bool {
     matches!(
         name,
-        "toString" | "startsWith" | "endsWith" | "contains" | "slice"
+        "toString"
+            | "startsWith"
+            | "endsWith"
+            | "contains"
+            | "slice"
+            | "isEmpty"
+            | "join"
+            | "unique"
+            | "sort"
+            | "reverse"
+            | "flat"
+            | "filter"
+            | "map"
+            | "containsAll"
+            | "containsAny"
+            | "keys"
+            | "values"
+            | "lower"
+            | "upper"
+            | "title"
+            | "trim"
+            | "replace"
+            | "split"
+            | "repeat"
+            | "matches"
     )
 }
 
+/// Largest string `.repeat()` builds; a formula must not exhaust memory.
+const MAX_REPEAT_BYTES: usize = 1 << 20;
+
 // ─── Evaluation ─────────────────────────────────────────────
 
 /// Context for `this.*` references — the .base file itself.
+///
+/// `ThisContext::default()` is an empty context for queries without a base file.
+#[derive(Debug, Clone, Default)]
 pub struct ThisContext {
     pub name: String,
     pub folder: String,
     pub rel_path: String,
-    pub properties: std::collections::HashMap<String, serde_yaml::Value>,
+    pub properties: HashMap<String, serde_yaml::Value>,
 }
 
 impl ThisContext {
@@ -418,321 +1008,1107 @@ impl ThisContext {
             name,
             folder,
             rel_path,
-            properties: std::collections::HashMap::new(),
+            properties: HashMap::new(),
+        }
+    }
+}
+
+/// Switches for how properties resolve, shared by filters, columns and sort keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvalOptions {
+    /// Let `note.key` fall back to the note's Dataview inline fields when its frontmatter
+    /// has no `key`, for migrating Dataview queries to Bases.
+    pub inline_fields: bool,
+}
+
+/// What expressions see besides the note under test: the `.base` file as `this`, and the
+/// loaded notes, so `file("Other.md").hasTag(...)` can read a note other than its own.
+pub struct EvalContext<'a> {
+    pub this: &'a ThisContext,
+    /// Notes file values are looked up in; with none, only the note under test is known.
+    pub notes: &'a [NoteContext],
+    pub options: EvalOptions,
+    by_path: HashMap<&'a str, &'a NoteContext>,
+}
+
+impl<'a> EvalContext<'a> {
+    pub fn new(this: &'a ThisContext, notes: &'a [NoteContext]) -> Self {
+        Self {
+            this,
+            notes,
+            options: EvalOptions::default(),
+            by_path: notes.iter().map(|n| (n.rel_path.as_str(), n)).collect(),
         }
     }
+
+    #[must_use]
+    pub fn with_options(mut self, options: EvalOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The loaded note at a vault-relative path.
+    pub fn note(&self, path: &str) -> Option<&'a NoteContext> {
+        self.by_path.get(path).copied()
+    }
 }
 
 /// Evaluate a filter expression string against a note.
-pub fn eval_filter(expr_str: &str, note: &NoteContext, this_ctx: &ThisContext) -> bool {
-    let tokens = tokenize(expr_str);
-    let mut parser = Parser::new(tokens);
-    if let Some(expr) = parser.parse_expr() {
-        eval_expr(&expr, note, this_ctx).as_bool()
-    } else {
-        eprintln!("warning: could not parse expression: {expr_str}");
-        true // permissive: include note if we can't parse the filter
+pub fn eval_filter(expr_str: &str, note: &NoteContext, ctx: &EvalContext) -> bool {
+    match parse_cached(expr_str) {
+        Some(expr) => eval_expr(&expr, note, ctx).as_bool(),
+        None => true, // permissive: include note if we can't parse the filter
     }
 }
 
-fn eval_expr(expr: &Expr, note: &NoteContext, this_ctx: &ThisContext) -> Val {
-    match expr {
-        Expr::Literal(v) => v.clone(),
+thread_local! {
+    /// Filters parsed once per thread: `resolve` evaluates every filter against every note,
+    /// and regex literals compile at parse time.
+    static PARSED: RefCell<HashMap<String, Option<Rc<Expr>>>> = RefCell::new(HashMap::new());
+}
 
-        Expr::Property(chain) => resolve_property(chain, note, this_ctx),
+/// Parse a filter, warning once if it is malformed; trailing tokens are ignored.
+fn parse_cached(expr_str: &str) -> Option<Rc<Expr>> {
+    PARSED.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(expr_str.to_owned())
+            .or_insert_with(|| {
+                let mut parser = Parser::from_source(expr_str);
+                let expr = parser.parse_expr();
+                match (expr, parser.error) {
+                    (Some(expr), None) => Some(Rc::new(expr)),
+                    (_, error) => {
+                        let reason = error.map(|e| format!(" ({e})")).unwrap_or_default();
+                        eprintln!("warning: could not parse expression: {expr_str}{reason}");
+                        None
+                    }
+                }
+            })
+            .clone()
+    })
+}
 
-        Expr::Not(inner) => Val::Bool(!eval_expr(inner, note, this_ctx).as_bool()),
+/// Evaluated expression tree: each node's source text and value, with its sub-expressions.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    pub expr: String,
+    pub value: Val,
+    pub children: Vec<Trace>,
+}
 
-        Expr::BinOp { op, left, right } => {
-            let l = eval_expr(left, note, this_ctx);
-            let r = eval_expr(right, note, this_ctx);
-            let result = match op {
-                BinOperator::Eq => l.to_string_val() == r.to_string_val(),
-                BinOperator::Neq => l.to_string_val() != r.to_string_val(),
-            };
-            Val::Bool(result)
-        }
+/// Evaluate a filter expression and record the value of every non-literal sub-expression.
+///
+/// Returns `None` when the expression cannot be parsed (`eval_filter` treats those as matches).
+pub fn explain(expr_str: &str, note: &NoteContext, ctx: &EvalContext) -> Option<Trace> {
+    let expr = parse_cached(expr_str)?;
+    Some(trace_expr(&expr, &Scope::new(note, ctx)))
+}
 
+fn trace_expr(expr: &Expr, scope: &Scope) -> Trace {
+    let subexprs: Vec<&Expr> = match expr {
+        Expr::Literal(_) | Expr::Property(_) => Vec::new(),
+        Expr::Field { receiver, .. } => vec![receiver.as_ref()],
+        Expr::Index { receiver, index } => vec![receiver.as_ref(), index.as_ref()],
+        // Lambda arguments only have a value per list item
         Expr::MethodCall {
-            receiver,
-            method,
-            args,
-        } => {
-            let recv = eval_expr(receiver, note, this_ctx);
-            eval_method(&recv, method, args, note, this_ctx)
+            receiver, method, ..
+        } if is_lambda_method(method) => vec![receiver.as_ref()],
+        Expr::MethodCall { receiver, args, .. } => {
+            std::iter::once(receiver.as_ref()).chain(args).collect()
         }
-
-        Expr::FuncCall {
-            name,
-            receiver,
-            args,
-        } => eval_func(name, receiver.as_deref(), args, note, this_ctx),
+        Expr::FuncCall { receiver, args, .. } => {
+            receiver.as_deref().into_iter().chain(args).collect()
+        }
+        Expr::BinOp { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+        Expr::Not(inner) | Expr::Neg(inner) => vec![inner.as_ref()],
+    };
+    Trace {
+        expr: expr.to_string(),
+        value: scope.eval(expr),
+        children: subexprs
+            .into_iter()
+            .filter(|e| !matches!(e, Expr::Literal(_)))
+            .map(|e| trace_expr(e, scope))
+            .collect(),
     }
 }
 
-fn resolve_property(chain: &[String], note: &NoteContext, this_ctx: &ThisContext) -> Val {
-    if chain.is_empty() {
-        return Val::Null;
+fn eval_expr(expr: &Expr, note: &NoteContext, ctx: &EvalContext) -> Val {
+    Scope::new(note, ctx).eval(expr)
+}
+
+/// Methods whose arguments are evaluated once per list item with `value` and `index` bound.
+fn is_lambda_method(name: &str) -> bool {
+    matches!(name, "filter" | "map")
+}
+
+/// Evaluation environment: the note under test, the .base file and loaded notes, and
+/// lambda bindings.
+struct Scope<'a> {
+    note: &'a NoteContext,
+    ctx: &'a EvalContext<'a>,
+    /// `value`/`index` bindings of enclosing `filter()`/`map()` calls, innermost last.
+    locals: Vec<(String, Val)>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(note: &'a NoteContext, ctx: &'a EvalContext<'a>) -> Self {
+        Self {
+            note,
+            ctx,
+            locals: Vec::new(),
+        }
     }
 
-    // Handle `this.*` references
-    if chain[0] == "this" {
-        return resolve_this(&chain[1..], this_ctx);
+    /// A child scope for one list item of a lambda method.
+    fn bind(&self, value: Val, index: usize) -> Scope<'a> {
+        let mut locals = self.locals.clone();
+        locals.push(("value".to_owned(), value));
+        locals.push(("index".to_owned(), count(index)));
+        Scope {
+            note: self.note,
+            ctx: self.ctx,
+            locals,
+        }
     }
 
-    // Handle `file.*` properties
-    if chain[0] == "file" {
-        return resolve_file_prop(&chain[1..], note);
+    fn local(&self, name: &str) -> Option<&Val> {
+        self.locals
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
     }
 
-    // Handle `property.*` — explicit frontmatter access
-    if chain[0] == "property" && chain.len() >= 2 {
-        return resolve_frontmatter(&chain[1..], note);
+    fn eval(&self, expr: &Expr) -> Val {
+        match expr {
+            Expr::Literal(v) => v.clone(),
+
+            Expr::Property(chain) => self.resolve_property(chain),
+
+            Expr::Field { receiver, name } => self.field(&self.eval(receiver), name),
+
+            Expr::Index { receiver, index } => index_val(&self.eval(receiver), &self.eval(index)),
+
+            Expr::Not(inner) => Val::Bool(!self.eval(inner).as_bool()),
+
+            Expr::Neg(inner) => to_number(&self.eval(inner)).map_or(Val::Null, |n| Val::Num(-n)),
+
+            // Logical operators short-circuit
+            Expr::BinOp {
+                op: BinOperator::And,
+                left,
+                right,
+            } => Val::Bool(self.eval(left).as_bool() && self.eval(right).as_bool()),
+            Expr::BinOp {
+                op: BinOperator::Or,
+                left,
+                right,
+            } => Val::Bool(self.eval(left).as_bool() || self.eval(right).as_bool()),
+
+            Expr::BinOp { op, left, right } => binary(*op, &self.eval(left), &self.eval(right)),
+
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+            } => {
+                let recv = self.eval(receiver);
+                self.eval_method(&recv, method, args)
+            }
+
+            Expr::FuncCall {
+                name,
+                receiver,
+                args,
+            } => self.eval_func(name, receiver.as_deref(), args),
+        }
     }
 
-    // Handle `note.*` — alias for property access
-    if chain[0] == "note" && chain.len() >= 2 {
-        return resolve_frontmatter(&chain[1..], note);
+    /// Evaluate the `i`th argument as an integer (`None` when missing or not a whole number).
+    fn arg_int(&self, args: &[Expr], i: usize) -> Option<i64> {
+        self.arg_str(args, i).parse().ok()
     }
 
-    // Bare property name — check frontmatter first, then fall back
-    if chain.len() == 1 {
-        let key = &chain[0];
-        if let Some(val) = note.get_property(key) {
-            return yaml_to_val(val);
+    /// Truncate `split()` results to the optional limit argument.
+    fn limit(&self, mut parts: Vec<Val>, args: &[Expr]) -> Vec<Val> {
+        if let Some(limit) = self.arg_int(args, 1) {
+            parts.truncate(usize::try_from(limit).unwrap_or(0));
         }
+        parts
     }
 
-    // Dotted bare property: created.toString() etc. — treat first part as property
-    if let Some(val) = note.get_property(&chain[0]) {
-        return yaml_to_val(val);
+    /// Evaluate the `i`th argument as a string (empty when missing).
+    fn arg_str(&self, args: &[Expr], i: usize) -> String {
+        args.get(i)
+            .map(|a| self.eval(a).to_string_val())
+            .unwrap_or_default()
     }
 
-    Val::Null
-}
+    fn resolve_property(&self, chain: &[String]) -> Val {
+        let Some((head, rest)) = chain.split_first() else {
+            return Val::Null;
+        };
 
-fn resolve_this(chain: &[String], this_ctx: &ThisContext) -> Val {
-    if chain.is_empty() {
-        return Val::Str(this_ctx.name.clone());
+        let (base, fields) = if let Some(val) = self.local(head) {
+            // Lambda bindings shadow frontmatter: `value`, `index`
+            (val.clone(), rest)
+        } else {
+            match head.as_str() {
+                "this" => resolve_this(rest, self.ctx.this),
+                "file" => resolve_file_prop(rest, self.note),
+                _ => lookup_property(self.note, chain, self.ctx.options)
+                    .map_or((Val::Null, &[][..]), |(val, rest)| (yaml_to_val(val), rest)),
+            }
+        };
+
+        fields.iter().fold(base, |val, name| self.field(&val, name))
+    }
+
+    /// The loaded note a file value refers to: the note under test or another loaded note.
+    fn note_at(&self, path: &str) -> Option<&'a NoteContext> {
+        if self.note.rel_path == path {
+            Some(self.note)
+        } else {
+            self.ctx.note(path)
+        }
+    }
+
+    /// Field access on a resolved value: `tags.length`, `file("x.md").folder`.
+    fn field(&self, val: &Val, name: &str) -> Val {
+        match val {
+            Val::File(path) => match self.note_at(path) {
+                Some(note) => file_prop(note, name),
+                None => path_prop(path, name),
+            },
+            Val::List(list) if name == "length" => count(list.len()),
+            Val::Object(obj) => obj.get(name).cloned().unwrap_or(Val::Null),
+            Val::Str(s) if name == "length" => count(s.chars().count()),
+            _ => Val::Null,
+        }
+    }
+
+    fn eval_method(&self, recv: &Val, method: &str, args: &[Expr]) -> Val {
+        let list_only = matches!(
+            method,
+            "join" | "unique" | "sort" | "flat" | "filter" | "map"
+        );
+        if let (Val::Object(obj), "keys" | "values" | "isEmpty") = (recv, method) {
+            return match method {
+                "keys" => Val::List(obj.keys().cloned().map(Val::Str).collect()),
+                "values" => Val::List(obj.values().cloned().collect()),
+                _ => Val::Bool(obj.is_empty()),
+            };
+        }
+        if list_only || matches!(recv, Val::List(_)) {
+            self.eval_list_method(items(recv), method, args)
+        } else {
+            self.eval_string_method(&recv.to_string_val(), method, args)
+        }
+    }
+
+    /// List methods; anything else applies to the list joined with `, `.
+    fn eval_list_method(&self, mut list: Vec<Val>, method: &str, args: &[Expr]) -> Val {
+        match method {
+            "contains" => Val::Bool(
+                args.first()
+                    .is_some_and(|a| list_contains(&list, &self.eval(a))),
+            ),
+
+            "containsAll" => Val::Bool(args.iter().all(|a| list_contains(&list, &self.eval(a)))),
+
+            "containsAny" => Val::Bool(args.iter().any(|a| list_contains(&list, &self.eval(a)))),
+
+            "isEmpty" => Val::Bool(list.is_empty()),
+
+            "join" => {
+                let parts: Vec<String> = list.iter().map(Val::to_string_val).collect();
+                Val::Str(parts.join(&self.arg_str(args, 0)))
+            }
+
+            "unique" => {
+                let mut seen = std::collections::HashSet::new();
+                list.retain(|item| seen.insert(item.to_string()));
+                Val::List(list)
+            }
+
+            "sort" => {
+                list.sort_by(sort_order);
+                Val::List(list)
+            }
+
+            "reverse" => {
+                list.reverse();
+                Val::List(list)
+            }
+
+            // One level: [[1, 2], 3] → [1, 2, 3]
+            "flat" => Val::List(list.iter().flat_map(items).collect()),
+
+            "filter" => {
+                if let Some(predicate) = args.first() {
+                    let mut index = 0;
+                    list.retain(|item| {
+                        index += 1;
+                        self.bind(item.clone(), index - 1).eval(predicate).as_bool()
+                    });
+                }
+                Val::List(list)
+            }
+
+            "map" => {
+                let Some(mapper) = args.first() else {
+                    return Val::List(list);
+                };
+                let list = list
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| self.bind(item, i).eval(mapper))
+                    .collect();
+                Val::List(list)
+            }
+
+            _ => self.eval_string_method(&Val::List(list).to_string_val(), method, args),
+        }
     }
 
-    if chain[0] == "file" {
-        return match chain.get(1).map(String::as_str) {
-            Some("fullname") => Val::Str(this_ctx.rel_path.clone()),
-            Some("folder") => Val::Str(this_ctx.folder.clone()),
-            _ => Val::Str(this_ctx.name.clone()), // "name" and fallback
+    fn eval_string_method(&self, s: &str, method: &str, args: &[Expr]) -> Val {
+        match method {
+            "toString" => Val::Str(s.to_owned()),
+
+            "startsWith" => Val::Bool(s.starts_with(&self.arg_str(args, 0))),
+
+            "endsWith" => Val::Bool(s.ends_with(&self.arg_str(args, 0))),
+
+            "contains" => Val::Bool(s.contains(&self.arg_str(args, 0))),
+
+            "containsAll" => Val::Bool(
+                args.iter()
+                    .all(|a| s.contains(&self.eval(a).to_string_val())),
+            ),
+
+            "containsAny" => Val::Bool(
+                args.iter()
+                    .any(|a| s.contains(&self.eval(a).to_string_val())),
+            ),
+
+            "isEmpty" => Val::Bool(s.is_empty()),
+
+            "slice" => {
+                let start = self.arg_int(args, 0).unwrap_or(0);
+                Val::Str(slice_chars(s, start, self.arg_int(args, 1)))
+            }
+
+            "lower" => Val::Str(s.to_lowercase()),
+
+            "upper" => Val::Str(s.to_uppercase()),
+
+            "title" => Val::Str(title_case(s)),
+
+            "trim" => Val::Str(s.trim().to_owned()),
+
+            "reverse" => Val::Str(s.chars().rev().collect()),
+
+            // Replaces every occurrence of a string or regex pattern
+            "replace" => {
+                let replacement = self.arg_str(args, 1);
+                match args.first().map(|a| self.eval(a)) {
+                    Some(Val::Regex { regex, .. }) => {
+                        Val::Str(regex.replace_all(s, replacement.as_str()).into_owned())
+                    }
+                    pattern => Val::Str(s.replace(
+                        &pattern.map(|p| p.to_string_val()).unwrap_or_default(),
+                        &replacement,
+                    )),
+                }
+            }
+
+            "split" => {
+                let sep = args.first().map_or(Val::Null, |a| self.eval(a));
+                let sep = match sep {
+                    Val::Regex { regex, .. } => {
+                        let parts = regex.split(s).map(|p| Val::Str(p.to_owned())).collect();
+                        return Val::List(self.limit(parts, args));
+                    }
+                    other => other.to_string_val(),
+                };
+                let parts: Vec<Val> = if sep.is_empty() {
+                    s.chars().map(|c| Val::Str(c.to_string())).collect()
+                } else {
+                    s.split(sep.as_str())
+                        .map(|part| Val::Str(part.to_owned()))
+                        .collect()
+                };
+                Val::List(self.limit(parts, args))
+            }
+
+            // Null rather than a huge allocation when the result would pass MAX_REPEAT_BYTES
+            "repeat" => {
+                let times = usize::try_from(self.arg_int(args, 0).unwrap_or(0)).unwrap_or(0);
+                match s.len().checked_mul(times) {
+                    Some(len) if len <= MAX_REPEAT_BYTES => Val::Str(s.repeat(times)),
+                    _ => Val::Null,
+                }
+            }
+
+            "matches" => Val::Bool(match args.first().map(|a| self.eval(a)) {
+                Some(Val::Regex { regex, .. }) => regex.is_match(s),
+                Some(pattern) => {
+                    cached_pattern(&pattern.to_string_val()).is_some_and(|re| re.is_match(s))
+                }
+                None => false,
+            }),
+
+            _ => {
+                eprintln!("warning: unknown method: {method}");
+                Val::Null
+            }
+        }
+    }
+
+    fn eval_func(&self, name: &str, receiver: Option<&Expr>, args: &[Expr]) -> Val {
+        let Some(receiver) = receiver else {
+            if let Some(func) = global_function(name) {
+                return func(self, args);
+            }
+            eprintln!("warning: unknown function: {name}");
+            return Val::Null;
         };
+        if let Val::File(path) = self.eval(receiver) {
+            return self.eval_file_method(&path, name, args);
+        }
+        eprintln!("warning: unknown function: {name}");
+        Val::Null
     }
 
-    // Bare property on this — check this_ctx.properties
-    if chain.len() == 1 {
-        if let Some(val) = this_ctx.properties.get(&chain[0]) {
-            return yaml_to_val(val);
+    fn eval_file_method(&self, path: &str, method: &str, args: &[Expr]) -> Val {
+        match method {
+            // file.inFolder("Projects") — also true for notes in subfolders
+            "inFolder" => {
+                let folder = self.arg_str(args, 0);
+                let folder = folder.trim_end_matches('/');
+                let own = path_prop(path, "folder").to_string_val();
+                Val::Bool(
+                    folder.is_empty() || own == folder || own.starts_with(&format!("{folder}/")),
+                )
+            }
+
+            // file.hasProperty("project.status") — present, even when empty
+            "hasProperty" => {
+                let chain: Vec<String> =
+                    self.arg_str(args, 0).split('.').map(String::from).collect();
+                Val::Bool(self.note_at(path).is_some_and(|note| {
+                    lookup_yaml(&chain, &note.properties).is_some_and(|(_, rest)| rest.is_empty())
+                }))
+            }
+
+            // file.hasTag("tag1", "tag2", ...) — true if any match
+            "hasTag" => Val::Bool(self.note_at(path).is_some_and(|note| {
+                args.iter()
+                    .any(|arg| note.has_tag(&self.eval(arg).to_string_val()))
+            })),
+
+            // file.hasLink(ref) — true if the note links to ref (a name, wikilink or file)
+            "hasLink" | "linksTo" => {
+                let target = args.first().map_or(Val::Null, |a| self.eval(a));
+                Val::Bool(
+                    self.note_at(path)
+                        .is_some_and(|note| link_targets(&target).iter().any(|t| note.has_link(t))),
+                )
+            }
+
+            // file.asLink(display?) — a link to the file
+            "asLink" => Val::Link {
+                target: path.strip_suffix(".md").unwrap_or(path).to_owned(),
+                display: args
+                    .first()
+                    .map(|a| self.eval(a).to_string_val())
+                    .filter(|d| !d.is_empty()),
+            },
+
+            _ => {
+                eprintln!("warning: unknown file method: {method}");
+                Val::Null
+            }
         }
     }
+}
 
-    Val::Null
+// ─── Global functions ───────────────────────────────────────
+
+/// A global function receives its arguments unevaluated, so `if()` only evaluates one branch.
+type GlobalFn = fn(&Scope, &[Expr]) -> Val;
+
+/// Functions callable without a receiver: `if(done, "✓", "")`, `max(a, b)`.
+const GLOBAL_FUNCTIONS: &[(&str, GlobalFn)] = &[
+    ("contains", global_contains),
+    ("if", global_if),
+    ("number", global_number),
+    ("list", global_list),
+    ("link", global_link),
+    ("file", global_file),
+    ("image", global_image),
+    ("icon", global_icon),
+    ("max", global_max),
+    ("min", global_min),
+    ("now", global_now),
+    ("today", global_today),
+];
+
+fn global_function(name: &str) -> Option<GlobalFn> {
+    GLOBAL_FUNCTIONS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, func)| *func)
 }
 
-fn resolve_file_prop(chain: &[String], note: &NoteContext) -> Val {
-    if chain.is_empty() {
-        return Val::Str(note.rel_path.clone());
+/// `contains(collection, value)` — substring match against the string or any list item.
+fn global_contains(scope: &Scope, args: &[Expr]) -> Val {
+    let coll = args.first().map_or(Val::Null, |a| scope.eval(a));
+    let target = scope.arg_str(args, 1);
+    match coll {
+        Val::List(list) => Val::Bool(
+            list.iter()
+                .any(|item| item.to_string_val().contains(&target)),
+        ),
+        Val::Str(s) => Val::Bool(s.contains(&target)),
+        _ => Val::Bool(false),
     }
-    match chain[0].as_str() {
-        "name" => Val::Str(note.name.clone()),
-        "path" | "fullname" => Val::Str(note.rel_path.clone()),
-        "ext" => Val::Str(note.ext.clone()),
-        "folder" => Val::Str(note.folder.clone()),
-        "tags" => Val::List(note.tags.clone()),
-        "links" => Val::List(note.links.clone()),
+}
+
+/// `if(condition, then, else?)` — `null` when the condition fails and there is no else branch.
+fn global_if(scope: &Scope, args: &[Expr]) -> Val {
+    let condition = args.first().is_some_and(|a| scope.eval(a).as_bool());
+    let branch = if condition { args.get(1) } else { args.get(2) };
+    branch.map_or(Val::Null, |a| scope.eval(a))
+}
+
+/// `number(x)` — numbers pass through, booleans become 1/0, numeric strings are parsed.
+fn global_number(scope: &Scope, args: &[Expr]) -> Val {
+    to_number(&args.first().map_or(Val::Null, |a| scope.eval(a))).map_or(Val::Null, Val::Num)
+}
+
+/// `list(x)` — lists pass through, anything else is wrapped in a one-item list.
+fn global_list(scope: &Scope, args: &[Expr]) -> Val {
+    Val::List(items(&args.first().map_or(Val::Null, |a| scope.eval(a))))
+}
+
+/// `link(path, display?)` — a link value; `link("[[X]]")` is the same as `link("X")`.
+fn global_link(scope: &Scope, args: &[Expr]) -> Val {
+    let target = match args.first().map_or(Val::Null, |a| scope.eval(a)) {
+        // By path, as `file.asLink()`: a bare name may belong to several notes
+        Val::File(path) => path.strip_suffix(".md").unwrap_or(&path).to_owned(),
+        other => link_targets(&other).swap_remove(0),
+    };
+    let display = args
+        .get(1)
+        .map(|a| scope.eval(a).to_string_val())
+        .filter(|d| !d.is_empty());
+    Val::Link { target, display }
+}
+
+/// `file(path)` — a file value; tags, links and properties come from the loaded note.
+fn global_file(scope: &Scope, args: &[Expr]) -> Val {
+    Val::File(scope.arg_str(args, 0))
+}
+
+/// `image(path)` — the image path; output is data only.
+fn global_image(scope: &Scope, args: &[Expr]) -> Val {
+    Val::Str(scope.arg_str(args, 0))
+}
+
+/// `icon(name)` — the icon name.
+fn global_icon(scope: &Scope, args: &[Expr]) -> Val {
+    Val::Str(scope.arg_str(args, 0))
+}
+
+fn global_max(scope: &Scope, args: &[Expr]) -> Val {
+    numbers(scope, args)
+        .reduce(f64::max)
+        .map_or(Val::Null, Val::Num)
+}
+
+fn global_min(scope: &Scope, args: &[Expr]) -> Val {
+    numbers(scope, args)
+        .reduce(f64::min)
+        .map_or(Val::Null, Val::Num)
+}
+
+/// Numeric arguments of `max`/`min`, with list arguments spread; non-numbers are skipped.
+fn numbers<'s>(scope: &'s Scope, args: &'s [Expr]) -> impl Iterator<Item = f64> + 's {
+    args.iter()
+        .flat_map(|a| items(&scope.eval(a)))
+        .filter_map(|item| match item {
+            Val::Num(n) => Some(n),
+            other => other.to_string_val().trim().parse::<f64>().ok(),
+        })
+}
+
+/// `now()` — local date and time as `YYYY-MM-DDTHH:MM:SS`, comparable with frontmatter datetimes.
+fn global_now(_: &Scope, _: &[Expr]) -> Val {
+    Val::Str(chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// `today()` — local date as `YYYY-MM-DD`, comparable with frontmatter dates.
+fn global_today(_: &Scope, _: &[Expr]) -> Val {
+    Val::Str(chrono::Local::now().format("%Y-%m-%d").to_string())
+}
+
+/// Numeric view of a value, shared by `number()` and the arithmetic operators.
+fn to_number(val: &Val) -> Option<f64> {
+    match val {
+        Val::Num(n) => Some(*n),
+        Val::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Val::Str(s) => s.trim().parse().ok(),
+        Val::List(_)
+        | Val::Object(_)
+        | Val::File(_)
+        | Val::Link { .. }
+        | Val::Regex { .. }
+        | Val::Null => None,
+    }
+}
+
+/// Apply a non-logical binary operator.
+///
+/// `+` adds numbers, concatenates lists and otherwise joins text; the other arithmetic
+/// operators need numeric operands. Anything involving `null`, and division or modulo
+/// by zero, yields `null`.
+fn binary(op: BinOperator, l: &Val, r: &Val) -> Val {
+    let arithmetic = |f: fn(f64, f64) -> f64| match (to_number(l), to_number(r)) {
+        (Some(_), Some(b)) if b == 0.0 && matches!(op, BinOperator::Div | BinOperator::Mod) => {
+            Val::Null
+        }
+        (Some(a), Some(b)) => Val::Num(f(a, b)),
         _ => Val::Null,
+    };
+    match op {
+        BinOperator::Eq => Val::Bool(values_equal(l, r)),
+        BinOperator::Neq => Val::Bool(!values_equal(l, r)),
+        BinOperator::Lt => Val::Bool(compare(l, r).is_some_and(Ordering::is_lt)),
+        BinOperator::Le => Val::Bool(compare(l, r).is_some_and(Ordering::is_le)),
+        BinOperator::Gt => Val::Bool(compare(l, r).is_some_and(Ordering::is_gt)),
+        BinOperator::Ge => Val::Bool(compare(l, r).is_some_and(Ordering::is_ge)),
+        BinOperator::And => Val::Bool(l.as_bool() && r.as_bool()),
+        BinOperator::Or => Val::Bool(l.as_bool() || r.as_bool()),
+        BinOperator::Add => match (l, r) {
+            (Val::Null, _) | (_, Val::Null) => Val::Null,
+            (Val::Num(a), Val::Num(b)) => Val::Num(a + b),
+            (Val::List(a), Val::List(b)) => Val::List(a.iter().chain(b).cloned().collect()),
+            _ => Val::Str(l.to_string_val() + &r.to_string_val()),
+        },
+        BinOperator::Sub => arithmetic(|a, b| a - b),
+        BinOperator::Mul => arithmetic(|a, b| a * b),
+        BinOperator::Div => arithmetic(|a, b| a / b),
+        BinOperator::Mod => arithmetic(|a, b| a % b),
+    }
+}
+
+/// Order two values: numerically when both parse as numbers, otherwise as strings.
+///
+/// Comparisons involving `Null` have no ordering, so `rating >= 4` is false for notes without a rating.
+fn compare(l: &Val, r: &Val) -> Option<Ordering> {
+    if matches!(l, Val::Null) || matches!(r, Val::Null) {
+        return None;
     }
+    Some(compare_text(&l.to_string_val(), &r.to_string_val()))
 }
 
-fn resolve_frontmatter(chain: &[String], note: &NoteContext) -> Val {
-    if chain.is_empty() {
-        return Val::Null;
+/// Total order for sorting, agreeing with `<` on values of the same kind: numbers (and
+/// numeric text) numerically, everything else as text. Numbers come before text and
+/// missing values last, so mixed lists still sort consistently.
+pub fn sort_order(l: &Val, r: &Val) -> Ordering {
+    let number = |v: &Val| match v {
+        Val::Num(n) => Some(*n),
+        other => other.to_string_val().parse::<f64>().ok(),
+    };
+    match (l, r) {
+        (Val::Null, Val::Null) => Ordering::Equal,
+        (Val::Null, _) => Ordering::Greater,
+        (_, Val::Null) => Ordering::Less,
+        _ => match (number(l), number(r)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => l.to_string_val().cmp(&r.to_string_val()),
+        },
     }
-    // Support dotted keys: property.item.owned → frontmatter key "item.owned" or nested
-    let key = chain.join(".");
-    if let Some(val) = note.get_property(&key) {
-        return yaml_to_val(val);
+}
+
+fn compare_text(l: &str, r: &str) -> Ordering {
+    match (l.parse::<f64>(), r.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => l.cmp(r),
+    }
+}
+
+/// A list method's receiver as items: missing values are empty, scalars a single item.
+fn items(val: &Val) -> Vec<Val> {
+    match val {
+        Val::List(list) => list.clone(),
+        Val::Null => Vec::new(),
+        other => vec![other.clone()],
     }
-    // Try first key, then traverse
-    if let Some(val) = note.get_property(&chain[0]) {
-        if chain.len() == 1 {
-            return yaml_to_val(val);
+}
+
+/// `list[i]` (negative counts from the end) or `obj["key"]`; `null` when out of range.
+fn index_val(recv: &Val, index: &Val) -> Val {
+    let found = match (recv, index) {
+        (Val::List(list), Val::Num(_)) => index
+            .to_string_val()
+            .parse::<i64>()
+            .ok()
+            .and_then(|i| {
+                let offset = usize::try_from(i.unsigned_abs()).ok()?;
+                if i < 0 {
+                    list.len().checked_sub(offset)
+                } else {
+                    Some(offset)
+                }
+            })
+            .and_then(|i| list.get(i)),
+        (Val::Object(obj), key) => obj.get(&key.to_string_val()),
+        _ => None,
+    };
+    found.cloned().unwrap_or(Val::Null)
+}
+
+fn count(n: usize) -> Val {
+    Val::Num(u32::try_from(n).map_or(f64::from(u32::MAX), f64::from))
+}
+
+/// Compile a regex literal's pattern and `/flags`; `g`, `u` and `y` don't affect matching.
+fn compile_regex(pattern: &str, flags: &str) -> Result<Regex, String> {
+    let mut inline = String::new();
+    for flag in flags.chars() {
+        match flag {
+            'i' | 'm' | 's' | 'x' => inline.push(flag),
+            'g' | 'u' | 'y' => {}
+            other => return Err(format!("unknown regex flag `{other}`")),
         }
-        // Nested access: property.item.owned → properties["item"]["owned"]
-        let mut current = val.clone();
-        for part in &chain[1..] {
-            match current.get(part.as_str()) {
-                Some(v) => current = v.clone(),
-                None => return Val::Null,
-            }
+    }
+    let full = if inline.is_empty() {
+        pattern.to_owned()
+    } else {
+        format!("(?{inline}){pattern}")
+    };
+    Regex::new(&full).map_err(|e| {
+        // The crate's message repeats the pattern; keep just the `error:` line
+        let text = e.to_string();
+        let reason = text
+            .lines()
+            .find_map(|l| l.strip_prefix("error: "))
+            .unwrap_or(text.trim());
+        format!("invalid regex: {reason}")
+    })
+}
+
+/// `matches()` patterns given as strings, compiled once per process.
+static PATTERNS: LazyLock<Mutex<HashMap<String, Option<Regex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Compile a string pattern through the cache, warning once if it is invalid.
+fn cached_pattern(pattern: &str) -> Option<Regex> {
+    let mut cache = PATTERNS.lock().unwrap_or_else(PoisonError::into_inner);
+    cache
+        .entry(pattern.to_owned())
+        .or_insert_with(|| {
+            compile_regex(pattern, "")
+                .map_err(|message| eprintln!("warning: {message} in {pattern:?}"))
+                .ok()
+        })
+        .clone()
+}
+
+fn wikilink(target: &str, display: Option<&str>) -> String {
+    match display {
+        Some(display) => format!("[[{target}|{display}]]"),
+        None => format!("[[{target}]]"),
+    }
+}
+
+/// Parse a wikilink-shaped string: `[[target]]` or `[[target|display]]`.
+fn parse_link(s: &str) -> Option<Val> {
+    let inner = s.trim().strip_prefix("[[")?.strip_suffix("]]")?;
+    if inner.is_empty() || inner.contains("[[") || inner.contains("]]") {
+        return None;
+    }
+    let (target, display) = match inner.split_once('|') {
+        Some((target, display)) => (target, Some(display.to_owned())),
+        None => (inner, None),
+    };
+    Some(Val::Link {
+        target: target.to_owned(),
+        display,
+    })
+}
+
+/// Names a link to this value may use: a file by name or extensionless path,
+/// a link or wikilink string by its target, anything else by its text.
+fn link_targets(val: &Val) -> Vec<String> {
+    match val {
+        Val::File(path) => vec![
+            path_prop(path, "name").to_string_val(),
+            path.strip_suffix(".md").unwrap_or(path).to_owned(),
+        ],
+        Val::Link { target, .. } => vec![target.clone()],
+        Val::Str(s) => match parse_link(s) {
+            Some(link) => link_targets(&link),
+            None => vec![s.clone()],
+        },
+        other => vec![other.to_string_val()],
+    }
+}
+
+/// Equality as used by `==` and list `contains`: a link equals another link, wikilink
+/// string, name or file with the same target. Everything else compares by text.
+fn values_equal(a: &Val, b: &Val) -> bool {
+    match (a, b) {
+        // A link with a folder names one file: only its full path may match, not a
+        // note of the same name elsewhere. A bare link name still matches by name.
+        (Val::Link { target, .. }, Val::File(path))
+        | (Val::File(path), Val::Link { target, .. })
+            if target.split('#').next().unwrap_or_default().contains('/') =>
+        {
+            same_note(path.strip_suffix(".md").unwrap_or(path), target)
         }
-        return yaml_to_val(&current);
+        (Val::Link { target, .. }, other) | (other, Val::Link { target, .. }) => {
+            link_targets(other).iter().any(|t| same_note(t, target))
+        }
+        _ => a.to_string_val() == b.to_string_val(),
     }
-    Val::Null
 }
 
-fn eval_method(
-    recv: &Val,
-    method: &str,
-    args: &[Expr],
-    note: &NoteContext,
-    this_ctx: &ThisContext,
-) -> Val {
-    match method {
-        "toString" => Val::Str(recv.to_string_val()),
+/// Whether two link targets name the same note: case-insensitive, ignoring `#heading`
+/// and `.md`, with a bare name matching any path that ends in it (`Alpha` ~ `Projects/Alpha`).
+fn same_note(a: &str, b: &str) -> bool {
+    let normalize = |t: &str| {
+        let t = t.split('#').next().unwrap_or_default();
+        t.strip_suffix(".md").unwrap_or(t).to_lowercase()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    let basename = |t: &str| t.rsplit('/').next().unwrap_or_default().to_owned();
+    a == b || (!a.contains('/') && basename(&b) == a) || (!b.contains('/') && basename(&a) == b)
+}
+
+fn list_contains(list: &[Val], target: &Val) -> bool {
+    list.iter().any(|item| values_equal(item, target))
+}
 
-        "startsWith" => {
-            let s = recv.to_string_val();
-            let prefix = args
-                .first()
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .unwrap_or_default();
-            Val::Bool(s.starts_with(&prefix))
+/// Slice by chars, not bytes; negative indices count from the end, as in JavaScript.
+fn slice_chars(s: &str, start: i64, end: Option<i64>) -> String {
+    let len = s.chars().count();
+    let clamp = |i: i64| {
+        let from_end = usize::try_from(i.unsigned_abs()).unwrap_or(usize::MAX);
+        if i < 0 {
+            len.saturating_sub(from_end)
+        } else {
+            from_end.min(len)
         }
+    };
+    let start = clamp(start);
+    let end = end.map_or(len, clamp);
+    s.chars()
+        .skip(start)
+        .take(end.saturating_sub(start))
+        .collect()
+}
 
-        "endsWith" => {
-            let s = recv.to_string_val();
-            let suffix = args
-                .first()
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .unwrap_or_default();
-            Val::Bool(s.ends_with(&suffix))
+/// Capitalize the first letter of every word and lowercase the rest.
+fn title_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut word_start = true;
+    for c in s.chars() {
+        if word_start {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
         }
+        word_start = c.is_whitespace();
+    }
+    out
+}
 
-        "contains" => match recv {
-            Val::List(list) => {
-                let target = args
-                    .first()
-                    .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                    .unwrap_or_default();
-                Val::Bool(list.iter().any(|item| item == &target))
-            }
-            Val::Str(s) => {
-                let needle = args
-                    .first()
-                    .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                    .unwrap_or_default();
-                Val::Bool(s.contains(&needle))
+/// Resolve `this.*`, returning the value and the chain parts left over as fields.
+fn resolve_this<'c>(chain: &'c [String], this_ctx: &ThisContext) -> (Val, &'c [String]) {
+    let Some((head, rest)) = chain.split_first() else {
+        return (Val::Str(this_ctx.name.clone()), chain);
+    };
+
+    if head == "file" {
+        return match rest.split_first() {
+            Some((prop, fields)) if prop == "fullname" || prop == "path" => {
+                (Val::Str(this_ctx.rel_path.clone()), fields)
             }
-            _ => Val::Bool(false),
-        },
+            Some((prop, fields)) if prop == "folder" => (Val::Str(this_ctx.folder.clone()), fields),
+            Some((prop, fields)) if prop == "name" => (Val::Str(this_ctx.name.clone()), fields),
+            // A file value, for `file.hasLink(this.file)` and `this.file.hasTag(...)`
+            _ => (Val::File(this_ctx.rel_path.clone()), rest),
+        };
+    }
 
-        "slice" => {
-            let s = recv.to_string_val();
-            let start = args
-                .first()
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .and_then(|v| v.parse::<usize>().ok())
-                .unwrap_or(0);
-            let end = args
-                .get(1)
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .and_then(|v| v.parse::<usize>().ok())
-                .unwrap_or(s.len());
-            let start = start.min(s.len());
-            let end = end.min(s.len());
-            Val::Str(s[start..end].to_owned())
-        }
-
-        _ => {
-            eprintln!("warning: unknown method: {method}");
-            Val::Null
-        }
+    // Property on this — check this_ctx.properties
+    resolve_frontmatter(chain, &this_ctx.properties)
+}
+
+fn resolve_file_prop<'c>(chain: &'c [String], note: &NoteContext) -> (Val, &'c [String]) {
+    match chain.split_first() {
+        Some((head, rest)) => (file_prop(note, head), rest),
+        None => (Val::File(note.rel_path.clone()), chain),
     }
 }
 
-fn eval_func(
-    name: &str,
-    receiver: Option<&Expr>,
-    args: &[Expr],
-    note: &NoteContext,
-    this_ctx: &ThisContext,
-) -> Val {
+fn file_prop(note: &NoteContext, name: &str) -> Val {
     match name {
-        // Global contains(collection, value)
-        "contains" if receiver.is_none() => {
-            let coll = args
-                .first()
-                .map_or(Val::Null, |a| eval_expr(a, note, this_ctx));
-            let target = args
-                .get(1)
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .unwrap_or_default();
-            match coll {
-                Val::List(list) => Val::Bool(list.iter().any(|item| item.contains(&target))),
-                Val::Str(s) => Val::Bool(s.contains(&target)),
-                _ => Val::Bool(false),
-            }
-        }
-
-        // file.hasTag("tag1", "tag2", ...) — true if any match
-        "hasTag" => {
-            for arg in args {
-                let tag = eval_expr(arg, note, this_ctx).to_string_val();
-                if note.has_tag(&tag) {
-                    return Val::Bool(true);
-                }
+        "name" => Val::Str(note.name.clone()),
+        "path" | "fullname" => Val::Str(note.rel_path.clone()),
+        "ext" => Val::Str(note.ext.clone()),
+        "folder" => Val::Str(note.folder.clone()),
+        "tags" => Val::List(note.tags.iter().cloned().map(Val::Str).collect()),
+        "links" => Val::List(
+            note.links
+                .iter()
+                .map(|target| Val::Link {
+                    target: target.clone(),
+                    display: None,
+                })
+                .collect(),
+        ),
+        _ => Val::Null,
+    }
+}
+
+/// File properties derivable from the path alone, for files other than the note under test.
+fn path_prop(path: &str, name: &str) -> Val {
+    let path_ref = Path::new(path);
+    let part = |p: Option<&std::ffi::OsStr>| p.unwrap_or_default().to_string_lossy().into_owned();
+    match name {
+        "name" => Val::Str(part(path_ref.file_stem())),
+        "path" | "fullname" => Val::Str(path.to_owned()),
+        "ext" => Val::Str(part(path_ref.extension())),
+        "folder" => Val::Str(part(path_ref.parent().map(Path::as_os_str))),
+        _ => Val::Null,
+    }
+}
+
+/// Resolve a frontmatter property, returning the value and the chain parts left over as fields.
+///
+/// The longest dotted key wins (`item.owned` may be a flat key), then nested mappings are
+/// traversed: `item.owned` → `properties["item"]["owned"]`.
+fn resolve_frontmatter<'c>(
+    chain: &'c [String],
+    properties: &HashMap<String, serde_yaml::Value>,
+) -> (Val, &'c [String]) {
+    match lookup_yaml(chain, properties) {
+        Some((val, rest)) => (yaml_to_val(val), rest),
+        None => (Val::Null, &[]),
+    }
+}
+
+/// The value a property chain names in a note, with the fields left to apply.
+///
+/// `note.*` and `property.*` are explicit frontmatter access; a bare name may be dotted or
+/// followed by fields (`keywords.length`). With `options.inline_fields`, `note.key` falls
+/// back to the note's inline fields when the frontmatter has no `key` or it is null.
+pub fn lookup_property<'n, 'c>(
+    note: &'n NoteContext,
+    chain: &'c [String],
+    options: EvalOptions,
+) -> Option<(&'n serde_yaml::Value, &'c [String])> {
+    match chain.split_first() {
+        Some((head, rest)) if head == "note" && !rest.is_empty() => {
+            let found = lookup_yaml(rest, &note.properties).filter(|(val, _)| !val.is_null());
+            if found.is_none() && options.inline_fields {
+                lookup_yaml(rest, note.inline_fields())
+            } else {
+                found
             }
-            Val::Bool(false)
         }
-
-        // file.hasLink(ref) — true if note links to ref
-        "hasLink" => {
-            let target = args
-                .first()
-                .map(|a| {
-                    let v = eval_expr(a, note, this_ctx);
-                    v.to_string_val()
-                })
-                .unwrap_or_default();
-            // If target is a this.file reference, use the name
-            Val::Bool(note.has_link(&target))
+        Some((head, rest)) if head == "property" && !rest.is_empty() => {
+            lookup_yaml(rest, &note.properties)
         }
+        _ => lookup_yaml(chain, &note.properties),
+    }
+}
 
-        // file.links.contains(ref)
-        "contains" if receiver.is_some() => {
-            let recv = eval_expr(receiver.unwrap(), note, this_ctx);
-            let target = args
-                .first()
-                .map(|a| eval_expr(a, note, this_ctx).to_string_val())
-                .unwrap_or_default();
-            match recv {
-                Val::List(list) => Val::Bool(list.contains(&target)),
-                Val::Str(s) => Val::Bool(s.contains(&target)),
-                _ => Val::Bool(false),
+/// Find the YAML value for the longest matching prefix of a property chain.
+fn lookup_yaml<'v, 'c>(
+    chain: &'c [String],
+    properties: &'v HashMap<String, serde_yaml::Value>,
+) -> Option<(&'v serde_yaml::Value, &'c [String])> {
+    for n in (1..=chain.len()).rev() {
+        let Some(mut current) = properties.get(&chain[..n].join(".")) else {
+            continue;
+        };
+        let mut rest = &chain[n..];
+        while let Some((part, tail)) = rest.split_first() {
+            match current.get(part.as_str()) {
+                Some(v) => {
+                    current = v;
+                    rest = tail;
+                }
+                None => break,
             }
         }
+        return Some((current, rest));
+    }
+    None
+}
 
-        _ => {
-            eprintln!("warning: unknown function: {name}");
-            Val::Null
-        }
+/// A task as an object: `task.status`, `task.text`, `task.due`, `task.completed`, ...
+impl From<&Task> for Val {
+    fn from(task: &Task) -> Self {
+        let date = |d: &Option<String>| d.clone().map_or(Val::Null, Val::Str);
+        Val::Object(BTreeMap::from([
+            ("status".to_owned(), Val::Str(task.status.to_string())),
+            ("text".to_owned(), Val::Str(task.text.clone())),
+            ("line".to_owned(), count(task.line)),
+            (
+                "section".to_owned(),
+                task.section.clone().map_or(Val::Null, Val::Str),
+            ),
+            (
+                "completed".to_owned(),
+                Val::Bool(task.status.eq_ignore_ascii_case(&'x')),
+            ),
+            ("closed".to_owned(), Val::Bool(task.is_closed())),
+            ("nested".to_owned(), Val::Bool(task.parent.is_some())),
+            ("due".to_owned(), date(&task.due)),
+            ("scheduled".to_owned(), date(&task.scheduled)),
+            ("start".to_owned(), date(&task.start)),
+            ("completion".to_owned(), date(&task.completion)),
+            ("created".to_owned(), date(&task.created)),
+        ]))
+    }
+}
+
+/// A frontmatter value: wikilink strings become links, mappings objects.
+impl From<&serde_yaml::Value> for Val {
+    fn from(val: &serde_yaml::Value) -> Self {
+        yaml_to_val(val)
     }
 }
 
 fn yaml_to_val(val: &serde_yaml::Value) -> Val {
     match val {
-        serde_yaml::Value::String(s) => Val::Str(s.clone()),
+        // Wikilink strings become links
+        serde_yaml::Value::String(s) => parse_link(s).unwrap_or_else(|| Val::Str(s.clone())),
         serde_yaml::Value::Bool(b) => Val::Bool(*b),
-        serde_yaml::Value::Number(n) => Val::Str(n.to_string()),
-        serde_yaml::Value::Sequence(seq) => {
-            let items: Vec<String> = seq
-                .iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect();
-            Val::List(items)
-        }
-        serde_yaml::Value::Null => Val::Null,
-        _ => Val::Str(
-            serde_yaml::to_string(val)
-                .unwrap_or_default()
-                .trim()
-                .to_owned(),
+        serde_yaml::Value::Number(n) => n.as_f64().map_or(Val::Null, Val::Num),
+        serde_yaml::Value::Sequence(seq) => Val::List(seq.iter().map(yaml_to_val).collect()),
+        serde_yaml::Value::Mapping(map) => Val::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let key = match yaml_to_val(k) {
+                        Val::Str(s) => s,
+                        other => other.to_string_val(),
+                    };
+                    (key, yaml_to_val(v))
+                })
+                .collect(),
         ),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_val(&tagged.value),
+        serde_yaml::Value::Null => Val::Null,
     }
 }
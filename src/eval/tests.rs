@@ -1,7 +1,12 @@
+// Pedantic lints the baseline tests predate
+#![allow(
+    clippy::needless_raw_string_hashes,
+    clippy::redundant_closure_for_method_calls
+)]
+
 use super::*;
 use crate::note::NoteContext;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 fn make_note(name: &str, folder: &str, tags: &[&str], links: &[&str]) -> NoteContext {
     let mut properties = HashMap::new();
@@ -15,17 +20,11 @@ fn make_note(name: &str, folder: &str, tags: &[&str], links: &[&str]) -> NoteCon
         serde_yaml::Value::String("2026-01-15".into()),
     );
 
-    NoteContext {
-        path: PathBuf::from(format!("/vault/{folder}/{name}.md")),
-        rel_path: format!("{folder}/{name}.md"),
-        name: name.to_owned(),
-        ext: "md".to_owned(),
-        folder: folder.to_owned(),
-        tags: tags.iter().map(|s| s.to_string()).collect(),
-        links: links.iter().map(|s| s.to_string()).collect(),
-        properties,
-        content: String::new(),
-    }
+    let mut note = NoteContext::from_content(&format!("{folder}/{name}.md"), "");
+    note.tags = tags.iter().map(|s| s.to_string()).collect();
+    note.links = links.iter().map(|s| s.to_string()).collect();
+    note.properties = properties;
+    note
 }
 
 fn make_this(name: &str, folder: &str) -> ThisContext {
@@ -37,6 +36,10 @@ fn make_this(name: &str, folder: &str) -> ThisContext {
     }
 }
 
+fn ctx(this: &ThisContext) -> EvalContext<'_> {
+    EvalContext::new(this, &[])
+}
+
 // ─── Tokenizer ──────────────────────────────────────────────
 
 #[test]
@@ -79,12 +82,12 @@ fn eval_contains_file_path() {
     assert!(eval_filter(
         r#"contains(file.path, "Inventory")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
     assert!(!eval_filter(
         r#"contains(file.path, "Contacts")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -92,7 +95,11 @@ fn eval_contains_file_path() {
 fn eval_contains_file_ext() {
     let note = make_note("Paint", "Notes", &[], &[]);
     let this = make_this("Test", "Notes");
-    assert!(eval_filter(r#"contains(file.ext, "md")"#, &note, &this));
+    assert!(eval_filter(
+        r#"contains(file.ext, "md")"#,
+        &note,
+        &ctx(&this)
+    ));
 }
 
 #[test]
@@ -102,12 +109,12 @@ fn eval_contains_property_tags() {
     assert!(eval_filter(
         r#"contains(property.tags, "type/item/painting")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
     assert!(!eval_filter(
         r#"contains(property.tags, "type/person")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -118,7 +125,7 @@ fn eval_file_fullname_neq_this() {
     assert!(eval_filter(
         "file.fullname != this.file.fullname",
         &note,
-        &this
+        &ctx(&this)
     ));
 
     // Same file should fail
@@ -127,7 +134,7 @@ fn eval_file_fullname_neq_this() {
     assert!(eval_filter(
         "file.fullname != this.file.fullname",
         &note,
-        &this_same
+        &ctx(&this_same)
     ));
 }
 
@@ -138,7 +145,7 @@ fn eval_starts_with() {
     assert!(eval_filter(
         r#"file.path.startsWith("Resources/Journals")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -149,14 +156,14 @@ fn eval_negation() {
     assert!(eval_filter(
         r#"!file.name.contains("template")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 
     let template = make_note("template", "Notes", &[], &[]);
     assert!(!eval_filter(
         r#"!file.name.contains("template")"#,
         &template,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -164,8 +171,16 @@ fn eval_negation() {
 fn eval_has_tag() {
     let note = make_note("Event", "Notes", &["type/event"], &[]);
     let this = make_this("Test", "Notes");
-    assert!(eval_filter(r#"file.hasTag("type/event")"#, &note, &this));
-    assert!(!eval_filter(r#"file.hasTag("type/project")"#, &note, &this));
+    assert!(eval_filter(
+        r#"file.hasTag("type/event")"#,
+        &note,
+        &ctx(&this)
+    ));
+    assert!(!eval_filter(
+        r#"file.hasTag("type/project")"#,
+        &note,
+        &ctx(&this)
+    ));
 }
 
 #[test]
@@ -175,7 +190,7 @@ fn eval_has_tag_multi() {
     assert!(eval_filter(
         r#"file.hasTag("type/project", "type/event")"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -183,7 +198,11 @@ fn eval_has_tag_multi() {
 fn eval_has_link() {
     let note = make_note("Daily", "Journals", &[], &["Project A", "Project B"]);
     let this = make_this("Project A", "Projects");
-    assert!(eval_filter(r#"file.hasLink(this.file)"#, &note, &this));
+    assert!(eval_filter(
+        r#"file.hasLink(this.file)"#,
+        &note,
+        &ctx(&this)
+    ));
 }
 
 #[test]
@@ -191,10 +210,9 @@ fn eval_method_chain_slice() {
     let note = make_note("Note", "Notes", &[], &[]);
     let this = make_this("2026-01-15", "Journals");
     // Test: this.file.name.slice(5, 10) should give "01-15"
-    let tokens = tokenize("this.file.name.slice(5, 10)");
-    let mut parser = Parser::new(tokens);
+    let mut parser = Parser::from_source("this.file.name.slice(5, 10)");
     let expr = parser.parse_expr().unwrap();
-    let result = eval_expr(&expr, &note, &this);
+    let result = eval_expr(&expr, &note, &ctx(&this));
     assert_eq!(result.to_string_val(), "01-15");
 }
 
@@ -205,7 +223,7 @@ fn eval_folder_starts_with() {
     assert!(eval_filter(
         r#"file.folder.startsWith(this.file.folder)"#,
         &note,
-        &this
+        &ctx(&this)
     ));
 }
 
@@ -213,5 +231,827 @@ fn eval_folder_starts_with() {
 fn eval_file_links_contains() {
     let note = make_note("Daily", "Journals", &[], &["MyProject"]);
     let this = make_this("MyProject", "Projects");
-    assert!(eval_filter("file.links.contains(this.file)", &note, &this));
+    assert!(eval_filter(
+        "file.links.contains(this.file)",
+        &note,
+        &ctx(&this)
+    ));
+}
+
+#[test]
+fn eval_numeric_comparisons() {
+    let mut note = make_note("Book", "Library", &[], &[]);
+    note.properties
+        .insert("rating".into(), serde_yaml::Value::Number(10.into()));
+    let this = make_this("Test", "Notes");
+    assert!(eval_filter("rating >= 4", &note, &ctx(&this)));
+    assert!(eval_filter("rating > 9", &note, &ctx(&this)));
+    assert!(!eval_filter("rating < 10", &note, &ctx(&this)));
+    assert!(eval_filter("rating <= 10", &note, &ctx(&this)));
+}
+
+#[test]
+fn eval_comparison_with_missing_property_is_false() {
+    let note = make_note("Book", "Library", &[], &[]);
+    let this = make_this("Test", "Notes");
+    assert!(!eval_filter("rating >= 4", &note, &ctx(&this)));
+    assert!(!eval_filter("rating < 4", &note, &ctx(&this)));
+}
+
+#[test]
+fn eval_string_comparison() {
+    let note = make_note("Book", "Library", &[], &[]);
+    let this = make_this("Test", "Notes");
+    assert!(eval_filter(r#"created > "2025-12-31""#, &note, &ctx(&this)));
+}
+
+#[test]
+fn explain_records_subexpression_values() {
+    let note = make_note("Note", "Notes", &[], &[]);
+    let this = make_this("Base", "Notes");
+    let trace = explain("file.fullname != this.file.fullname", &note, &ctx(&this)).unwrap();
+    assert_eq!(trace.expr, "file.fullname != this.file.fullname");
+    assert!(trace.value.as_bool());
+    assert_eq!(trace.children.len(), 2);
+    assert_eq!(trace.children[0].value.to_string(), "\"Notes/Note.md\"");
+    assert_eq!(trace.children[1].value.to_string(), "\"Notes/Base.base\"");
+}
+
+#[test]
+fn explain_skips_literals() {
+    let note = make_note("Note", "Notes", &[], &[]);
+    let this = make_this("Base", "Notes");
+    let trace = explain(r#"contains(file.path, "Notes")"#, &note, &ctx(&this)).unwrap();
+    assert_eq!(trace.expr, r#"contains(file.path, "Notes")"#);
+    assert_eq!(trace.children.len(), 1);
+    assert_eq!(trace.children[0].expr, "file.path");
+}
+
+// ─── Strict parsing ────────────────────────────────────────
+
+#[test]
+fn expression_parse_and_eval() {
+    let note = make_note("Paint", "Notes", &["type/item"], &[]);
+    let this = make_this("Test", "Notes");
+    let expr = Expression::parse(r#"file.hasTag("type/item")"#).unwrap();
+    assert!(expr.matches(&note, &ctx(&this)));
+    assert_eq!(expr.to_string(), r#"file.hasTag("type/item")"#);
+}
+
+#[test]
+fn expression_reports_missing_paren() {
+    let err = Expression::parse(r#"contains(file.path, "x""#).unwrap_err();
+    assert_eq!(err.message, "expected `)`");
+    assert_eq!(err.offset, 23);
+}
+
+#[test]
+fn expression_rejects_trailing_tokens() {
+    let err = Expression::parse("file.name file.path").unwrap_err();
+    assert_eq!(err.message, "unexpected identifier `file`");
+    assert_eq!(err.offset, 10);
+}
+
+#[test]
+fn parse_error_caret() {
+    let source = "== 4";
+    let err = Expression::parse(source).unwrap_err();
+    assert_eq!(err.offset, 0);
+    assert_eq!(err.caret(source), "== 4\n^ unexpected `==`");
+}
+
+// ─── List methods ──────────────────────────────────────────
+
+fn with_list(key: &str, items: &[&str]) -> NoteContext {
+    let mut note = make_note("Note", "Notes", &[], &[]);
+    let seq = items
+        .iter()
+        .map(|s| serde_yaml::from_str::<serde_yaml::Value>(s).unwrap())
+        .collect();
+    note.properties
+        .insert(key.to_owned(), serde_yaml::Value::Sequence(seq));
+    note
+}
+
+fn eval_str(source: &str, note: &NoteContext) -> String {
+    let this = make_this("Base", "Notes");
+    Expression::parse(source)
+        .unwrap()
+        .eval(note, &ctx(&this))
+        .to_string()
+}
+
+#[test]
+fn list_length_and_is_empty() {
+    let note = with_list("keywords", &["rust", "obsidian", "rust"]);
+    assert_eq!(eval_str("keywords.length", &note), "3");
+    assert_eq!(eval_str("keywords.unique().length", &note), "2");
+    assert_eq!(eval_str("keywords.isEmpty()", &note), "false");
+    assert_eq!(eval_str("missing.isEmpty()", &note), "true");
+    assert_eq!(eval_str("file.tags.length", &note), "0");
+}
+
+#[test]
+fn list_join_sort_reverse_unique() {
+    let note = with_list("keywords", &["b", "a", "c", "a"]);
+    assert_eq!(eval_str(r#"keywords.join("-")"#, &note), r#""b-a-c-a""#);
+    assert_eq!(eval_str("keywords.unique()", &note), r#"["b", "a", "c"]"#);
+    assert_eq!(
+        eval_str("keywords.sort()", &note),
+        r#"["a", "a", "b", "c"]"#
+    );
+    assert_eq!(
+        eval_str("keywords.sort().reverse()", &note),
+        r#"["c", "b", "a", "a"]"#
+    );
+}
+
+#[test]
+fn list_sort_is_numeric_for_numbers() {
+    let note = with_list("scores", &["10", "9", "100"]);
+    assert_eq!(eval_str("scores.sort()", &note), "[9, 10, 100]");
+}
+
+#[test]
+fn list_filter_and_map_bind_value_and_index() {
+    let note = with_list("scores", &["1", "5", "3"]);
+    assert_eq!(eval_str("scores.filter(value > 2)", &note), "[5, 3]");
+    assert_eq!(eval_str("scores.filter(index >= 1)", &note), "[5, 3]");
+    assert_eq!(
+        eval_str("scores.map(value.toString())", &note),
+        r#"["1", "5", "3"]"#
+    );
+    let note = with_list("related", &["\"[[Alpha]]\"", "\"[[Beta]]\""]);
+    assert_eq!(
+        eval_str(r#"related.filter(value.contains("Beta")).length"#, &note),
+        "1"
+    );
+}
+
+#[test]
+fn list_contains_all_and_any() {
+    let note = with_list("keywords", &["rust", "cli"]);
+    assert_eq!(
+        eval_str(r#"keywords.containsAll("rust", "cli")"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"keywords.containsAll("rust", "go")"#, &note),
+        "false"
+    );
+    assert_eq!(
+        eval_str(r#"keywords.containsAny("go", "cli")"#, &note),
+        "true"
+    );
+    assert_eq!(eval_str(r#"keywords.containsAny("go")"#, &note), "false");
+}
+
+#[test]
+fn explain_does_not_trace_lambda_arguments() {
+    let note = with_list("scores", &["1", "5"]);
+    let this = make_this("Base", "Notes");
+    let trace = explain("scores.filter(value > 2).length == 1", &note, &ctx(&this)).unwrap();
+    assert!(trace.value.as_bool());
+    let filtered = &trace.children[0].children[0];
+    assert_eq!(filtered.expr, "scores.filter(value > 2)");
+    assert_eq!(filtered.children.len(), 1);
+    assert_eq!(filtered.children[0].expr, "scores");
+}
+
+// ─── String methods ────────────────────────────────────────
+
+fn named(name: &str) -> NoteContext {
+    make_note(name, "Notes", &[], &[])
+}
+
+#[test]
+fn string_slice_is_char_aware() {
+    let note = named("Žluťoučký kůň");
+    assert_eq!(eval_str("file.name.slice(0, 4)", &note), r#""Žluť""#);
+    assert_eq!(eval_str("file.name.slice(10)", &note), r#""kůň""#);
+    assert_eq!(eval_str("file.name.slice(-3)", &note), r#""kůň""#);
+    assert_eq!(eval_str("file.name.slice(4, 2)", &note), r#""""#);
+    assert_eq!(
+        eval_str("file.name.slice(0, 99)", &note),
+        r#""Žluťoučký kůň""#
+    );
+}
+
+#[test]
+fn string_case_and_length() {
+    let note = named("příliš žluťoučký KŮŇ");
+    assert_eq!(
+        eval_str("file.name.upper()", &note),
+        r#""PŘÍLIŠ ŽLUŤOUČKÝ KŮŇ""#
+    );
+    assert_eq!(
+        eval_str("file.name.lower()", &note),
+        r#""příliš žluťoučký kůň""#
+    );
+    assert_eq!(
+        eval_str("file.name.title()", &note),
+        r#""Příliš Žluťoučký Kůň""#
+    );
+    assert_eq!(eval_str("file.name.length", &note), "20");
+    assert_eq!(
+        eval_str("file.name.reverse().slice(0, 3)", &note),
+        r#""ŇŮK""#
+    );
+}
+
+#[test]
+fn string_trim_replace_repeat() {
+    let mut note = named("Note");
+    note.properties.insert(
+        "status".into(),
+        serde_yaml::Value::String("  in-progress ".into()),
+    );
+    assert_eq!(eval_str("status.trim()", &note), r#""in-progress""#);
+    assert_eq!(
+        eval_str(r#"status.trim().replace("-", " ")"#, &note),
+        r#""in progress""#
+    );
+    assert_eq!(eval_str(r#""ab".repeat(3)"#, &note), r#""ababab""#);
+    assert_eq!(
+        eval_str("file.name.repeat(4611686018427387904)", &note),
+        "null"
+    );
+    assert_eq!(eval_str(r#""ab".repeat(1000000)"#, &note), "null");
+    assert_eq!(eval_str("status.isEmpty()", &note), "false");
+    assert_eq!(eval_str(r#""".isEmpty()"#, &note), "true");
+}
+
+#[test]
+fn string_split() {
+    let note = named("2026-01-15");
+    assert_eq!(
+        eval_str(r#"file.name.split("-")"#, &note),
+        r#"["2026", "01", "15"]"#
+    );
+    assert_eq!(eval_str(r#"file.name.split("-", 1)"#, &note), r#"["2026"]"#);
+    assert_eq!(eval_str(r#""kůň".split("")"#, &note), r#"["k", "ů", "ň"]"#);
+}
+
+#[test]
+fn string_matches_regex() {
+    let note = named("2026-01-15");
+    assert_eq!(
+        eval_str(r#"file.name.matches("^\d{4}-\d{2}-\d{2}$")"#, &note),
+        "true"
+    );
+    assert_eq!(eval_str(r#"file.name.matches("^Daily")"#, &note), "false");
+    let err = Expression::parse(r#"file.name.matches("(")"#).unwrap_err();
+    assert_eq!(err.message, "invalid regex: unclosed group");
+    assert_eq!(err.offset, 18);
+}
+
+// ─── Global functions ──────────────────────────────────────
+
+#[test]
+fn global_if_evaluates_one_branch() {
+    let mut note = named("Note");
+    note.properties
+        .insert("done".into(), serde_yaml::Value::Bool(true));
+    assert_eq!(eval_str(r#"if(done, "yes", "no")"#, &note), r#""yes""#);
+    assert_eq!(eval_str(r#"if(!done, "yes", "no")"#, &note), r#""no""#);
+    assert_eq!(eval_str(r#"if(missing, "yes")"#, &note), "null");
+}
+
+#[test]
+fn global_number_and_list() {
+    let mut note = named("Note");
+    note.properties
+        .insert("pages".into(), serde_yaml::Value::String(" 320 ".into()));
+    assert_eq!(eval_str("number(pages)", &note), "320");
+    assert_eq!(eval_str("number(pages) > 99", &note), "true");
+    assert_eq!(eval_str(r#"number("abc")"#, &note), "null");
+    assert_eq!(eval_str("list(file.name)", &note), r#"["Note"]"#);
+    assert_eq!(eval_str("list(file.tags)", &note), "[]");
+}
+
+#[test]
+fn global_max_and_min_spread_lists() {
+    let note = with_list("scores", &["3", "12", "x"]);
+    assert_eq!(eval_str("max(scores, 4)", &note), "12");
+    assert_eq!(eval_str("min(scores, 4)", &note), "3");
+    assert_eq!(eval_str("max()", &note), "null");
+}
+
+#[test]
+fn global_link_file_icon() {
+    let note = named("Note");
+    assert_eq!(
+        eval_str(r#"link("Projects/Alpha")"#, &note),
+        "[[Projects/Alpha]]"
+    );
+    assert_eq!(
+        eval_str(r#"link("Projects/Alpha", "Alpha")"#, &note),
+        "[[Projects/Alpha|Alpha]]"
+    );
+    assert_eq!(
+        eval_str(r#"file("Notes/Idea.md")"#, &note),
+        r#"file("Notes/Idea.md")"#
+    );
+    assert_eq!(eval_str(r#"icon("star")"#, &note), r#""star""#);
+}
+
+#[test]
+fn global_today_and_now() {
+    let note = named("Note");
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert_eq!(eval_str("today()", &note), format!("{today:?}"));
+    assert!(eval_str("now()", &note).starts_with(&format!("\"{today}T")));
+    assert_eq!(eval_str(r#"today() > "2000-01-01""#, &note), "true");
+}
+
+// ─── File objects ──────────────────────────────────────────
+
+#[test]
+fn file_in_folder_includes_subfolders() {
+    let note = make_note("Child", "Projects/Alpha", &[], &[]);
+    assert_eq!(eval_str(r#"file.inFolder("Projects")"#, &note), "true");
+    assert_eq!(
+        eval_str(r#"file.inFolder("Projects/Alpha/")"#, &note),
+        "true"
+    );
+    assert_eq!(eval_str(r#"file.inFolder("Proj")"#, &note), "false");
+    assert_eq!(eval_str(r#"file.inFolder("Notes")"#, &note), "false");
+}
+
+#[test]
+fn file_has_property_flat_and_nested() {
+    let mut note = named("Alpha");
+    note.properties.insert(
+        "project".into(),
+        serde_yaml::from_str("{status: active, owner: null}").unwrap(),
+    );
+    note.properties
+        .insert("item.owned".into(), serde_yaml::Value::Bool(true));
+    assert_eq!(
+        eval_str(r#"file.hasProperty("project.status")"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"file.hasProperty("project.owner")"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"file.hasProperty("project.due")"#, &note),
+        "false"
+    );
+    assert_eq!(eval_str(r#"file.hasProperty("item.owned")"#, &note), "true");
+    assert_eq!(eval_str(r#"file.hasProperty("status")"#, &note), "false");
+}
+
+#[test]
+fn file_has_link_accepts_files_and_wikilinks() {
+    let note = make_note("Daily", "Journals", &[], &["Alpha", "Projects/Beta"]);
+    assert_eq!(eval_str(r#"file.hasLink("[[Alpha|A]]")"#, &note), "true");
+    assert_eq!(
+        eval_str(r#"file.hasLink(file("Projects/Alpha/Alpha.md"))"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"file.linksTo(file("Projects/Beta.md"))"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"file.linksTo(file("Gamma.md"))"#, &note),
+        "false"
+    );
+}
+
+#[test]
+fn file_values_are_objects() {
+    let note = make_note("Idea", "Notes", &["idea"], &[]);
+    assert_eq!(eval_str("file", &note), r#"file("Notes/Idea.md")"#);
+    assert_eq!(eval_str("file.asLink()", &note), "[[Notes/Idea]]");
+    assert_eq!(
+        eval_str(r#"file.asLink("Idea")"#, &note),
+        "[[Notes/Idea|Idea]]"
+    );
+    assert_eq!(
+        eval_str(r#"file("Notes/Idea.md").hasTag("idea")"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"file("Other/Plan.md").folder"#, &note),
+        r#""Other""#
+    );
+    assert_eq!(
+        eval_str(r#"file("Other/Plan.md").name"#, &note),
+        r#""Plan""#
+    );
+    assert_eq!(
+        eval_str(r#"file("Other/Plan.md").hasTag("idea")"#, &note),
+        "false"
+    );
+}
+
+#[test]
+fn file_values_read_other_loaded_notes() {
+    let notes = [
+        make_note("Idea", "Notes", &["idea"], &[]),
+        make_note("Plan", "Other", &["type/project"], &["Idea"]),
+        make_note("Hub", "Projects", &["type/hub"], &[]),
+    ];
+    let this = ThisContext {
+        name: "Hub".into(),
+        folder: "Projects".into(),
+        rel_path: "Projects/Hub.md".into(),
+        ..ThisContext::default()
+    };
+    let ctx = EvalContext::new(&this, &notes);
+    let eval = |source: &str| {
+        Expression::parse(source)
+            .unwrap()
+            .eval(&notes[0], &ctx)
+            .to_string()
+    };
+
+    assert_eq!(
+        eval(r#"file("Other/Plan.md").hasTag("type/project")"#),
+        "true"
+    );
+    assert_eq!(eval(r#"file("Other/Plan.md").hasLink(file)"#), "true");
+    assert_eq!(eval(r#"file("Other/Plan.md").tags"#), r#"["type/project"]"#);
+    assert_eq!(eval("this.file"), r#"file("Projects/Hub.md")"#);
+    assert_eq!(eval("this.file.folder"), r#""Projects""#);
+    assert_eq!(eval(r#"this.file.hasTag("type/hub")"#), "true");
+}
+
+// ─── Links ─────────────────────────────────────────────────
+
+#[test]
+fn frontmatter_wikilinks_are_links() {
+    let mut note = named("Note");
+    note.properties.insert(
+        "author".into(),
+        serde_yaml::Value::String("[[Andrew Hunt|Hunt]]".into()),
+    );
+    assert_eq!(eval_str("author", &note), "[[Andrew Hunt|Hunt]]");
+    assert_eq!(eval_str(r#"author == "Andrew Hunt""#, &note), "true");
+    assert_eq!(eval_str(r#"author == "[[andrew hunt]]""#, &note), "true");
+    assert_eq!(eval_str(r#"author == link("Andrew Hunt")"#, &note), "true");
+    assert_eq!(eval_str(r#"author.contains("Hunt")"#, &note), "true");
+}
+
+#[test]
+fn link_lists_match_files_and_names() {
+    let note = with_list(
+        "related",
+        &["\"[[Obsidian]]\"", "\"[[Projects/Claude#Setup]]\""],
+    );
+    let this = make_this("Obsidian", "Tools");
+    let related = |source: &str| {
+        Expression::parse(source)
+            .unwrap()
+            .eval(&note, &ctx(&this))
+            .to_string()
+    };
+    assert_eq!(related("related.contains(this.file)"), "true");
+    assert_eq!(
+        related(r#"related.contains(file("Tools/Obsidian.md"))"#),
+        "true"
+    );
+    assert_eq!(
+        related(r#"related.contains(file("Projects/Claude.md"))"#),
+        "true"
+    );
+    assert_eq!(related(r#"related.contains(link("Claude"))"#), "true");
+    assert_eq!(
+        related(r#"related.containsAny(link("Obsidian"), "Vim")"#),
+        "true"
+    );
+    assert_eq!(
+        related(r#"related.filter(value == link("Obsidian")).length"#),
+        "1"
+    );
+}
+
+#[test]
+fn link_equals_file() {
+    let note = make_note("Alpha", "Projects/Alpha", &[], &[]);
+    assert_eq!(eval_str(r#"link("Alpha") == file"#, &note), "true");
+    assert_eq!(
+        eval_str(r#"link("Projects/Alpha/Alpha") == file"#, &note),
+        "true"
+    );
+    assert_eq!(
+        eval_str(r#"link("[[Alpha]]") == file.asLink()"#, &note),
+        "true"
+    );
+    assert_eq!(eval_str(r#"link("Beta") != file"#, &note), "true");
+    assert_eq!(eval_str("link(file)", &note), "[[Projects/Alpha/Alpha]]");
+    assert_eq!(
+        eval_str(r#"link(file) == link("Archive/Alpha")"#, &note),
+        "false"
+    );
+}
+
+#[test]
+fn link_to_a_path_only_equals_that_file() {
+    let notes = [
+        make_note("Alpha", "Notes", &[], &[]),
+        make_note("Alpha", "Projects", &[], &[]),
+    ];
+    let this = make_this("Hub", "Projects");
+    let ctx = EvalContext::new(&this, &notes);
+    let eval = |source: &str| {
+        Expression::parse(source)
+            .unwrap()
+            .eval(&notes[0], &ctx)
+            .to_string()
+    };
+
+    assert_eq!(
+        eval(r#"file("Notes/Alpha.md") == link("Projects/Alpha")"#),
+        "false"
+    );
+    assert_eq!(
+        eval(r#"file("Projects/Alpha.md") == link("Projects/Alpha")"#),
+        "true"
+    );
+    assert_eq!(eval(r#"link("Notes/Alpha") == file"#), "true");
+    assert_eq!(eval(r#"link("Projects/Alpha") == file"#), "false");
+    // A bare name still matches either note
+    assert_eq!(
+        eval(r#"file("Projects/Alpha.md") == link("Alpha")"#),
+        "true"
+    );
+    assert_eq!(eval(r#"link("Alpha") == file"#), "true");
+}
+
+// ─── Lists and objects ─────────────────────────────────────
+
+fn with_yaml(key: &str, yaml: &str) -> NoteContext {
+    let mut note = named("Note");
+    note.properties
+        .insert(key.to_owned(), serde_yaml::from_str(yaml).unwrap());
+    note
+}
+
+#[test]
+fn lists_keep_numbers_and_booleans() {
+    let note = with_yaml("ratings", "[4, 5, true, x]");
+    assert_eq!(eval_str("ratings", &note), r#"[4, 5, true, "x"]"#);
+    assert_eq!(eval_str("ratings.length", &note), "4");
+    assert_eq!(eval_str("max(ratings)", &note), "5");
+    assert_eq!(eval_str("ratings.contains(5)", &note), "true");
+}
+
+#[test]
+fn list_index_access() {
+    let note = with_yaml("related", r#"["[[Alpha]]", "[[Beta]]"]"#);
+    assert_eq!(eval_str("related[0]", &note), "[[Alpha]]");
+    assert_eq!(eval_str("related[-1]", &note), "[[Beta]]");
+    assert_eq!(eval_str("related[2]", &note), "null");
+    assert_eq!(eval_str(r#"link("Alpha") == related[0]"#, &note), "true");
+    assert_eq!(eval_str("related[1].toString().length", &note), "8");
+}
+
+#[test]
+fn flat_flattens_one_level() {
+    let note = with_yaml("groups", "[[1, 2], [3, [4]], 5]");
+    assert_eq!(eval_str("groups.flat()", &note), "[1, 2, 3, [4], 5]");
+    assert_eq!(eval_str("groups[1][1][0]", &note), "4");
+}
+
+#[test]
+fn object_access_keys_and_values() {
+    let note = with_yaml("project", "{status: active, priority: 2}");
+    assert_eq!(
+        eval_str("project", &note),
+        r#"{"priority": 2, "status": "active"}"#
+    );
+    assert_eq!(eval_str(r#"project["status"]"#, &note), r#""active""#);
+    assert_eq!(eval_str("project.priority", &note), "2");
+    assert_eq!(
+        eval_str("project.keys()", &note),
+        r#"["priority", "status"]"#
+    );
+    assert_eq!(eval_str("project.values()", &note), r#"[2, "active"]"#);
+    assert_eq!(eval_str("project.isEmpty()", &note), "false");
+    assert_eq!(eval_str(r#"project["missing"]"#, &note), "null");
+}
+
+#[test]
+fn index_expressions_round_trip() {
+    let expr = Expression::parse(r#"project["status"] == related[0]"#).unwrap();
+    assert_eq!(expr.to_string(), r#"project["status"] == related[0]"#);
+    let err = Expression::parse("related[0").unwrap_err();
+    assert_eq!(err.message, "expected `]`");
+}
+
+// ─── Regex literals ────────────────────────────────────────
+
+#[test]
+fn tokenize_regex_literal_only_where_operand_expected() {
+    let tokens = tokenize(r"file.name.matches(/^\d+\/x/i)");
+    assert!(tokens.contains(&Token::Regex {
+        pattern: r"^\d+/x".into(),
+        flags: "i".into(),
+    }));
+    // After an operand a slash divides
+    let tokens = tokenize(r#"file.hasTag("a") == type/project"#);
+    assert_eq!(
+        tokens[tokens.len() - 3..],
+        [
+            Token::Ident("type".into()),
+            Token::Slash,
+            Token::Ident("project".into())
+        ]
+    );
+}
+
+#[test]
+fn regex_literal_matching_and_flags() {
+    let note = named("2026-01-15 Daily");
+    assert_eq!(
+        eval_str(r"file.name.matches(/^\d{4}-\d{2}-\d{2}/)", &note),
+        "true"
+    );
+    assert_eq!(eval_str(r"file.name.matches(/daily$/)", &note), "false");
+    assert_eq!(eval_str(r"file.name.matches(/daily$/i)", &note), "true");
+    assert_eq!(eval_str(r"/daily/gi", &note), "/daily/gi");
+}
+
+#[test]
+fn regex_replace_and_split() {
+    let note = named("2026-01-15 Daily");
+    assert_eq!(
+        eval_str(r#"file.name.replace(/\d/, "x")"#, &note),
+        r#""xxxx-xx-xx Daily""#
+    );
+    assert_eq!(
+        eval_str(
+            r#"file.name.replace(/(\d+)-(\d+)-(\d+)/, "$3.$2.$1")"#,
+            &note
+        ),
+        r#""15.01.2026 Daily""#
+    );
+    assert_eq!(
+        eval_str(r"file.name.split(/[- ]/)", &note),
+        r#"["2026", "01", "15", "Daily"]"#
+    );
+}
+
+#[test]
+fn invalid_regex_literals_are_parse_errors() {
+    let err = Expression::parse("file.name.matches(/[a-/)").unwrap_err();
+    assert!(err.message.starts_with("invalid regex: "));
+    assert_eq!(err.offset, 18);
+    let err = Expression::parse("file.name.matches(/a/q)").unwrap_err();
+    assert_eq!(err.message, "unknown regex flag `q`");
+    // Lenient filters treat unparseable expressions as matches
+    let note = named("Note");
+    let this = make_this("Base", "Notes");
+    assert!(eval_filter("file.name.matches(/[a-/)", &note, &ctx(&this)));
+}
+
+// ─── Lexer and operators ───────────────────────────────────
+
+#[test]
+fn lex_records_spans() {
+    let tokens = lex(r#"a.b == "x y""#).unwrap();
+    let spans: Vec<(usize, usize)> = tokens.iter().map(|t| (t.start, t.end)).collect();
+    assert_eq!(spans, vec![(0, 1), (1, 2), (2, 3), (4, 6), (7, 12)]);
+}
+
+#[test]
+fn lex_string_escapes() {
+    assert_eq!(
+        tokenize(r#""say \"hi\"\n" 'it\'s' "\d+""#),
+        vec![
+            Token::Str("say \"hi\"\n".into()),
+            Token::Str("it's".into()),
+            Token::Str(r"\d+".into()),
+        ]
+    );
+}
+
+#[test]
+fn lex_numbers_and_keywords() {
+    assert_eq!(
+        tokenize("1.5 2e3 7.x true null"),
+        vec![
+            Token::Num(1.5),
+            Token::Num(2000.0),
+            Token::Num(7.0),
+            Token::Dot,
+            Token::Ident("x".into()),
+            Token::Bool(true),
+            Token::Null,
+        ]
+    );
+}
+
+#[test]
+fn lex_slash_is_division_with_or_without_spaces() {
+    assert_eq!(
+        tokenize("a / b/c"),
+        vec![
+            Token::Ident("a".into()),
+            Token::Slash,
+            Token::Ident("b".into()),
+            Token::Slash,
+            Token::Ident("c".into()),
+        ]
+    );
+}
+
+#[test]
+fn unspaced_slash_divides_numeric_properties() {
+    let mut note = named("Note");
+    note.properties
+        .insert("price".into(), serde_yaml::Value::from(12));
+    note.properties
+        .insert("qty".into(), serde_yaml::Value::from(4));
+    assert_eq!(eval_str("price/qty", &note), "3");
+    assert_eq!(eval_str("price/qty == price / qty", &note), "true");
+}
+
+#[test]
+fn lex_errors_point_at_the_offending_character() {
+    let err = Expression::parse("file.name @ 1").unwrap_err();
+    assert_eq!(
+        (err.message.as_str(), err.offset),
+        ("unexpected character `@`", 10)
+    );
+    let err = Expression::parse(r#"file.name == "open"#).unwrap_err();
+    assert_eq!(
+        (err.message.as_str(), err.offset),
+        ("unterminated string", 13)
+    );
+    let err = Expression::parse("file.name = 1").unwrap_err();
+    assert_eq!(err.message, "unexpected `=`, use `==`");
+}
+
+#[test]
+fn arithmetic_follows_precedence() {
+    let note = with_yaml("rating", "4");
+    assert_eq!(eval_str("1 + 2 * 3", &note), "7");
+    assert_eq!(eval_str("(1 + 2) * 3", &note), "9");
+    assert_eq!(eval_str("note.rating / 2 - 0.5", &note), "1.5");
+    assert_eq!(eval_str("7 % 3", &note), "1");
+    assert_eq!(eval_str("-note.rating", &note), "-4");
+    assert_eq!(eval_str("1 / 0", &note), "null");
+    assert_eq!(eval_str("note.missing + 1", &note), "null");
+    assert_eq!(eval_str(r#""v" + 1.5"#, &note), r#""v1.5""#);
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    let note = with_yaml("rating", "4");
+    assert_eq!(
+        eval_str("note.rating > 3 && note.rating < 5", &note),
+        "true"
+    );
+    assert_eq!(eval_str("note.rating > 5 || !false", &note), "true");
+    assert_eq!(eval_str("false && note.missing.length > 0", &note), "false");
+    assert_eq!(eval_str("true == !null", &note), "true");
+}
+
+#[test]
+fn operators_round_trip_with_minimal_parentheses() {
+    for source in [
+        "(a + b) * c",
+        "a + b * c",
+        "a - (b - c)",
+        "!(a == b) || c && d",
+        "(a + b).toString()",
+    ] {
+        assert_eq!(Expression::parse(source).unwrap().to_string(), source);
+    }
+}
+
+#[test]
+fn note_prefix_falls_back_to_inline_fields_when_enabled() {
+    let note = NoteContext::from_content(
+        "Library/Book.md",
+        "---\ncreated: 2026-01-15\n---\nrating:: 4\ncreated:: 1999-01-01\n",
+    );
+    let this = make_this("Base", "Notes");
+    let rating = Expression::parse("note.rating").unwrap();
+    assert!(matches!(rating.eval(&note, &ctx(&this)), Val::Null));
+    assert!(matches!(
+        Expression::parse("rating")
+            .unwrap()
+            .eval(&note, &ctx(&this)),
+        Val::Null
+    ));
+
+    let inline = EvalOptions {
+        inline_fields: true,
+    };
+    let with_inline = ctx(&this).with_options(inline);
+    assert_eq!(rating.eval(&note, &with_inline).to_string(), "4");
+    // Frontmatter wins over an inline field of the same name
+    assert_eq!(
+        Expression::parse("note.created")
+            .unwrap()
+            .eval(&note, &with_inline)
+            .to_string(),
+        "\"2026-01-15\""
+    );
 }
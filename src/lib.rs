@@ -1,4 +1,8 @@
 pub mod base;
 pub mod eval;
+pub mod lint;
 pub mod note;
+pub mod output;
+pub mod projects;
+pub mod schema;
 pub mod vault;
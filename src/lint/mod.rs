@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod tests;
+
+use crate::note::{self, Frontmatter, NoteContext};
+use serde_json::json;
+use serde_yaml::Value;
+use std::fmt::{self, Write};
+
+/// Which frontmatter convention a finding breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// `project: {status: x}` instead of `project.status: x`.
+    NestedObject,
+    /// A list whose items are objects or lists.
+    ListOfObjects,
+    /// A bare value containing `:`, which must be quoted.
+    UnquotedColon,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rule::NestedObject => "nested-object",
+            Rule::ListOfObjects => "list-of-objects",
+            Rule::UnquotedColon => "unquoted-colon",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// 1-based line in the note.
+    pub line: usize,
+    pub rule: Rule,
+    pub message: String,
+    /// Whether `fix` rewrites it.
+    pub fixable: bool,
+}
+
+/// Findings for one note, as reported by `lint_notes`.
+#[derive(Debug, Clone)]
+pub struct NoteReport {
+    pub path: String,
+    pub findings: Vec<Finding>,
+}
+
+/// Lint every note, keeping only the ones with findings.
+pub fn lint_notes(notes: &[NoteContext]) -> Vec<NoteReport> {
+    let mut reports: Vec<NoteReport> = notes
+        .iter()
+        .map(|note| NoteReport {
+            path: note.rel_path.clone(),
+            findings: lint_note(note),
+        })
+        .filter(|r| !r.findings.is_empty())
+        .collect();
+    reports.sort_by(|a, b| a.path.cmp(&b.path));
+    reports
+}
+
+/// One `path:line: [rule] message` line per finding, then a summary.
+pub fn render_human(reports: &[NoteReport]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        for f in &report.findings {
+            let hint = if f.fixable { " (fixable)" } else { "" };
+            let _ = writeln!(
+                out,
+                "{}:{}: [{}] {}{hint}",
+                report.path, f.line, f.rule, f.message
+            );
+        }
+    }
+    let total: usize = reports.iter().map(|r| r.findings.len()).sum();
+    let _ = writeln!(out, "{total} finding(s) in {} note(s)", reports.len());
+    out
+}
+
+/// A JSON array with one object per finding.
+pub fn render_json(reports: &[NoteReport]) -> String {
+    let findings: Vec<serde_json::Value> = reports
+        .iter()
+        .flat_map(|r| {
+            r.findings.iter().map(|f| {
+                json!({
+                    "file": r.path,
+                    "line": f.line,
+                    "rule": f.rule.to_string(),
+                    "message": f.message,
+                    "fixable": f.fixable,
+                })
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&findings).unwrap_or_default() + "\n"
+}
+
+/// Check a note against the flat-frontmatter rule: values are strings or lists of strings,
+/// never nested objects, and values containing `:` are quoted.
+///
+/// Structure is checked on the parsed properties, quoting on the raw YAML text (a bare
+/// `title: Note: subtitle` does not parse at all).
+pub fn lint_note(note: &NoteContext) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = unquoted_colons(note.content())
+        .into_iter()
+        .map(|(index, _)| Finding {
+            line: index + 1,
+            rule: Rule::UnquotedColon,
+            message: "value contains `:` and must be quoted".to_owned(),
+            fixable: true,
+        })
+        .collect();
+
+    let mut keys: Vec<&String> = note.properties.keys().collect();
+    keys.sort();
+    for key in keys {
+        let line = note::frontmatter_line(note.content(), key);
+        match &note.properties[key] {
+            Value::Mapping(mapping) if !mapping.is_empty() => findings.push(Finding {
+                line,
+                rule: Rule::NestedObject,
+                message: format!("`{key}` is a nested object; use flat keys like `{key}.name`"),
+                fixable: true,
+            }),
+            Value::Sequence(items)
+                if items
+                    .iter()
+                    .any(|i| matches!(i, Value::Mapping(_) | Value::Sequence(_))) =>
+            {
+                findings.push(Finding {
+                    line,
+                    rule: Rule::ListOfObjects,
+                    message: format!("`{key}` lists objects or lists; use a list of strings"),
+                    fixable: false,
+                });
+            }
+            _ => {}
+        }
+    }
+    findings.sort_by_key(|f| f.line);
+    findings
+}
+
+/// Apply every fixable finding: quote bare colon values, then flatten nested objects.
+///
+/// Unfixable findings are left for a human; flattening is skipped when a dotted key it
+/// would create already exists.
+pub fn fix(content: &str) -> Result<String, String> {
+    let fixes = unquoted_colons(content);
+    let quoted = if fixes.is_empty() {
+        content.to_owned()
+    } else {
+        content
+            .split_inclusive('\n')
+            .enumerate()
+            .map(
+                |(i, line)| match fixes.iter().find(|(index, _)| *index == i) {
+                    Some((_, quoted_line)) => {
+                        let eol = &line[line.trim_end_matches(['\r', '\n']).len()..];
+                        format!("{quoted_line}{eol}")
+                    }
+                    None => line.to_owned(),
+                },
+            )
+            .collect()
+    };
+
+    let mut frontmatter = Frontmatter::parse(&quoted)?;
+    for key in frontmatter.keys() {
+        frontmatter.flatten(&key);
+    }
+    Ok(frontmatter.render())
+}
+
+/// Frontmatter lines holding a bare value with `:`, as (0-based index, quoted line).
+///
+/// Without a closing `---` there is no frontmatter, only a note starting with a rule.
+fn unquoted_colons(content: &str) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.first().is_none_or(|first| first.trim_end() != "---") {
+        return Vec::new();
+    }
+    let Some(close) = lines[1..].iter().position(|line| line.trim_end() == "---") else {
+        return Vec::new();
+    };
+    let mut fixes = Vec::new();
+    // Indent of the line that opened a `|` or `>` block scalar, while inside one
+    let mut block: Option<usize> = None;
+    for (i, line) in lines.iter().enumerate().take(close + 1).skip(1) {
+        let indent = line.len() - line.trim_start().len();
+        if block.is_some_and(|open| line.trim().is_empty() || indent > open) {
+            continue;
+        }
+        block = opens_block_scalar(line).then_some(indent);
+        if let Some(fixed) = quote_colon_value(line) {
+            fixes.push((i, fixed));
+        }
+    }
+    fixes
+}
+
+/// Whether a `key: |` or `- >-` line starts a block scalar, whose indented lines that
+/// follow are literal text.
+fn opens_block_scalar(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let value = match trimmed.strip_prefix("- ") {
+        Some(item) => item.split_once(": ").map_or(item, |(_, v)| v),
+        None => trimmed.split_once(": ").map_or("", |(_, v)| v),
+    };
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    value.starts_with(['|', '>'])
+        && value[1..]
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '+' || c == '-')
+}
+
+/// Quote the value of a `key: value` or `- item` line when it is bare and contains `:`.
+fn quote_colon_value(line: &str) -> Option<String> {
+    let (prefix, value) = if let Some(item) = line.trim_start().strip_prefix("- ") {
+        let value_start = line.len() - item.len();
+        // `- key: value` is an object item, which the list-of-objects rule reports
+        if item.contains(": ") || item.trim_end().ends_with(':') {
+            return None;
+        }
+        (&line[..value_start], item)
+    } else if line.starts_with([' ', '\t', '#']) {
+        return None;
+    } else {
+        let colon = line.find(": ")?;
+        (&line[..colon + 2], &line[colon + 2..])
+    };
+
+    let value = value.trim_start();
+    let end = value.find(" #").unwrap_or(value.len());
+    let (value, comment) = value.split_at(value[..end].trim_end().len());
+    let bare = !value.starts_with(['"', '\'', '[', '{', '|', '>', '&', '*', '!']);
+    (bare && value.contains(':')).then(|| {
+        let quoted = serde_json::to_string(value).unwrap_or_default();
+        format!("{prefix}{quoted}{comment}")
+    })
+}
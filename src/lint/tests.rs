@@ -0,0 +1,82 @@
+use super::*;
+
+fn note(content: &str) -> NoteContext {
+    NoteContext::from_content("Note.md", content)
+}
+
+fn rules(content: &str) -> Vec<(usize, Rule)> {
+    lint_note(&note(content))
+        .into_iter()
+        .map(|f| (f.line, f.rule))
+        .collect()
+}
+
+#[test]
+fn flat_frontmatter_passes() {
+    let content = "---\ntitle: \"Meeting: Q3\"\nproject.status: active\nsource: 'https://example.com'\ntags:\n  - type/project\n  - \"at: home\"\ndescription: |\n  Notes: free text\n---\nBody: with colons\n";
+    assert_eq!(rules(content), []);
+}
+
+#[test]
+fn flags_nested_objects_and_lists_of_objects() {
+    let content = "---\ntitle: A\nproject:\n  status: active\nsessions:\n  - date: 2026-01-01\n    length: 2\n---\n";
+    let findings = lint_note(&note(content));
+    assert_eq!(
+        findings
+            .iter()
+            .map(|f| (f.line, f.rule))
+            .collect::<Vec<_>>(),
+        [(3, Rule::NestedObject), (5, Rule::ListOfObjects)]
+    );
+    assert!(findings[0].fixable);
+    assert!(!findings[1].fixable);
+}
+
+#[test]
+fn flags_unquoted_colon_values() {
+    let content =
+        "---\ntitle: Meeting: Q3\nurl: https://example.com  # source\naliases:\n  - https://example.org\n---\n";
+    assert_eq!(
+        rules(content),
+        [
+            (2, Rule::UnquotedColon),
+            (3, Rule::UnquotedColon),
+            (5, Rule::UnquotedColon)
+        ]
+    );
+}
+
+#[test]
+fn block_scalars_are_literal_text() {
+    let content = "---\ndescription: |\n  Steps:\n  - see https://example.com\n\n  done: yes\nsummary: >- # folded\n  - at: home\nurl: https://example.com\n---\n";
+    assert_eq!(rules(content), [(9, Rule::UnquotedColon)]);
+    assert_eq!(
+        fix(content).unwrap(),
+        content.replace("url: https://example.com", "url: \"https://example.com\"")
+    );
+}
+
+#[test]
+fn unclosed_frontmatter_has_no_colon_findings() {
+    let content = "---\nThe plan: ship it\nNext step: review\n";
+    assert_eq!(rules(content), []);
+    assert_eq!(unquoted_colons(content), []);
+}
+
+#[test]
+fn fix_quotes_and_flattens() {
+    let content = "---\ntitle: Meeting: Q3\nurl: https://example.com  # source\nproject: {status: active, due: 2026-03-01}\n---\n# Body: untouched\n";
+    let fixed = fix(content).unwrap();
+    assert_eq!(
+        fixed,
+        "---\ntitle: \"Meeting: Q3\"\nurl: \"https://example.com\"  # source\nproject.status: active\nproject.due: 2026-03-01\n---\n# Body: untouched\n"
+    );
+    assert_eq!(rules(&fixed), []);
+    assert_eq!(fix(&fixed).unwrap(), fixed);
+}
+
+#[test]
+fn fix_leaves_lists_of_objects_alone() {
+    let content = "---\nsessions:\n  - date: 2026-01-01\n---\n";
+    assert_eq!(unquoted_colons(content), []);
+}
@@ -0,0 +1,411 @@
+#[cfg(test)]
+mod tests;
+
+use serde_yaml::{Mapping, Value};
+use std::ops::Range;
+
+/// Editable frontmatter of a note.
+///
+/// Edits rewrite only the entries they touch: other keys keep their order, comments and
+/// quoting, and everything after the closing `---` is reproduced byte-for-byte. Keys are
+/// top-level; a dotted name such as `project.status` is a flat key, as the vault writes them.
+#[derive(Debug, Clone)]
+pub struct Frontmatter {
+    /// Lines between the fences, without line endings.
+    lines: Vec<String>,
+    /// Everything after the closing fence, or the whole note when it had no frontmatter.
+    rest: String,
+    /// Whether the note had a frontmatter block; one is added on the first edit otherwise.
+    present: bool,
+    eol: &'static str,
+}
+
+impl Frontmatter {
+    /// Split a note into frontmatter lines and the untouched remainder.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let eol = if content.starts_with("---\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let Some(after_open) = content.strip_prefix(&format!("---{eol}")) else {
+            return Ok(Self {
+                lines: Vec::new(),
+                rest: content.to_owned(),
+                present: false,
+                eol,
+            });
+        };
+
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        loop {
+            let remaining = &after_open[offset..];
+            if remaining.is_empty() {
+                return Err("unterminated frontmatter (no closing `---`)".to_owned());
+            }
+            let len = remaining.find('\n').map_or(remaining.len(), |i| i + 1);
+            let line = remaining[..len].trim_end_matches(['\n', '\r']);
+            if line == "---" {
+                let closing = offset + "---".len();
+                let frontmatter = Self {
+                    lines,
+                    rest: after_open[closing..].to_owned(),
+                    present: true,
+                    eol,
+                };
+                frontmatter.mapping()?;
+                return Ok(frontmatter);
+            }
+            lines.push(line.to_owned());
+            offset += len;
+        }
+    }
+
+    /// Reassemble the note.
+    pub fn render(&self) -> String {
+        if !self.present && self.lines.is_empty() {
+            return self.rest.clone();
+        }
+        let mut out = format!("---{}", self.eol);
+        for line in &self.lines {
+            out.push_str(line);
+            out.push_str(self.eol);
+        }
+        out.push_str("---");
+        if !self.present {
+            out.push_str(self.eol);
+        }
+        out.push_str(&self.rest);
+        out
+    }
+
+    /// Property value, parsed from the entry's YAML.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let range = self.entry(key)?;
+        let yaml = self.lines[range].join("\n");
+        let Ok(Value::Mapping(mut mapping)) = serde_yaml::from_str(&yaml) else {
+            return None;
+        };
+        mapping.remove(key)
+    }
+
+    /// Whether the frontmatter has a top-level `key`.
+    pub fn contains(&self, key: &str) -> bool {
+        self.entry(key).is_some()
+    }
+
+    /// Top-level keys in document order.
+    pub fn keys(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| parse_key(line).map(|(key, _)| key))
+            .collect()
+    }
+
+    /// Set `key` to `value`, replacing its entry in place or appending a new one.
+    ///
+    /// A replaced string keeps its quote style, and an inline comment on the key line survives.
+    pub fn set(&mut self, key: &str, value: &Value) {
+        let Some(range) = self.entry(key) else {
+            let lines = render_entry(&format_key(key), value, &EntryStyle::default());
+            self.lines.extend(lines);
+            return;
+        };
+        let line = &self.lines[range.start];
+        let (_, key_end) = parse_key(line).unwrap_or_default();
+        let style = EntryStyle::of(&self.lines[range.clone()], key_end);
+        let lines = render_entry(&line[..key_end], value, &style);
+        self.lines.splice(range, lines);
+    }
+
+    /// Remove `key` and its nested lines. Returns whether it was present.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(range) = self.entry(key) else {
+            return false;
+        };
+        self.lines.drain(range);
+        true
+    }
+
+    /// Rename `from` to `to`, keeping the value, position and formatting.
+    ///
+    /// Returns whether `from` was present; renaming onto an existing key is an error.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<bool, String> {
+        let Some(range) = self.entry(from) else {
+            return Ok(false);
+        };
+        if from != to && self.contains(to) {
+            return Err(format!("property `{to}` already exists"));
+        }
+        let line = &mut self.lines[range.start];
+        let (_, key_end) = parse_key(line).unwrap_or_default();
+        *line = format!("{}{}", format_key(to), &line[key_end..]);
+        Ok(true)
+    }
+
+    /// Append `value` to the list at `key`, creating the list or promoting a scalar to one.
+    ///
+    /// Block and flow lists keep their style. Returns false when the value was already present.
+    pub fn append(&mut self, key: &str, value: &Value) -> bool {
+        let items = match self.get(key) {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Sequence(items)) => items,
+            Some(scalar) => vec![scalar],
+        };
+        if items.contains(value) {
+            return false;
+        }
+        if let Some(range) = self.entry(key) {
+            let entry = &self.lines[range.clone()];
+            let (_, key_end) = parse_key(&entry[0]).unwrap_or_default();
+            let style = EntryStyle::of(entry, key_end);
+            if let Some(prefix) = style.item_prefix {
+                let line = format!("{prefix}{}", render_scalar(value, None));
+                self.lines.insert(range.end, line);
+                return true;
+            }
+            if let Some(line) = append_flow(&entry[0], key_end, value) {
+                self.lines[range.start] = line;
+                return true;
+            }
+        }
+        let mut items = items;
+        items.push(value.clone());
+        self.set(key, &Value::Sequence(items));
+        true
+    }
+
+    /// Replace a nested object with flat dotted keys in its place, so `project: {status: x}`
+    /// becomes `project.status: x`. Deeper objects flatten to `a.b.c`.
+    ///
+    /// Returns false when `key` is not an object or a flattened key already exists.
+    pub fn flatten(&mut self, key: &str) -> bool {
+        let Some(Value::Mapping(mapping)) = self.get(key) else {
+            return false;
+        };
+        let mut flat = Vec::new();
+        flatten_into(key, mapping, &mut flat);
+        if flat.iter().any(|(k, _)| self.contains(k)) {
+            return false;
+        }
+        let Some(range) = self.entry(key) else {
+            return false;
+        };
+        let lines = flat
+            .iter()
+            .flat_map(|(k, v)| render_entry(&format_key(k), v, &EntryStyle::default()))
+            .collect::<Vec<_>>();
+        self.lines.splice(range, lines);
+        true
+    }
+
+    /// The frontmatter as a YAML mapping; empty frontmatter is an empty mapping.
+    fn mapping(&self) -> Result<Mapping, String> {
+        let yaml = self.lines.join("\n");
+        match serde_yaml::from_str::<Value>(&yaml) {
+            Ok(Value::Mapping(mapping)) => Ok(mapping),
+            Ok(Value::Null) => Ok(Mapping::new()),
+            Ok(_) => Err("frontmatter is not a YAML mapping".to_owned()),
+            Err(e) => Err(format!("invalid frontmatter: {e}")),
+        }
+    }
+
+    /// Line range of a top-level entry: its key line plus indented or list continuation lines.
+    fn entry(&self, key: &str) -> Option<Range<usize>> {
+        let start = self
+            .lines
+            .iter()
+            .position(|line| parse_key(line).is_some_and(|(k, _)| k == key))?;
+        let mut end = start + 1;
+        while self
+            .lines
+            .get(end)
+            .is_some_and(|line| is_continuation(line))
+        {
+            end += 1;
+        }
+        // Blank lines before the next key separate entries rather than belong to this one
+        while end > start + 1 && self.lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        Some(start..end)
+    }
+}
+
+fn flatten_into(prefix: &str, mapping: Mapping, flat: &mut Vec<(String, Value)>) {
+    for (key, value) in mapping {
+        let key = match key {
+            Value::String(s) => s,
+            other => serde_yaml::to_string(&other)
+                .unwrap_or_default()
+                .trim_end()
+                .to_owned(),
+        };
+        let key = format!("{prefix}.{key}");
+        match value {
+            Value::Mapping(nested) if !nested.is_empty() => flatten_into(&key, nested, flat),
+            value => flat.push((key, value)),
+        }
+    }
+}
+
+fn is_continuation(line: &str) -> bool {
+    line.trim().is_empty() || line.starts_with([' ', '\t', '-'])
+}
+
+/// Parse a top-level `key:` line into the unquoted key and the byte offset after the key text.
+fn parse_key(line: &str) -> Option<(String, usize)> {
+    if line.is_empty() || line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    let quote = line.chars().next().filter(|c| matches!(c, '"' | '\''));
+    let (key, end) = if let Some(quote) = quote {
+        let close = line[1..].find(quote)? + 1;
+        (line[1..close].to_owned(), close + 1)
+    } else {
+        let end = line
+            .match_indices(':')
+            .map(|(i, _)| i)
+            .find(|&i| line[i + 1..].is_empty() || line[i + 1..].starts_with([' ', '\t']))?;
+        (line[..end].trim_end().to_owned(), end)
+    };
+    line[end..]
+        .trim_start()
+        .starts_with(':')
+        .then_some((key, end))
+}
+
+/// Render a key, quoting it only when YAML would otherwise misread it.
+fn format_key(key: &str) -> String {
+    let plain = serde_yaml::to_string(key).unwrap_or_default();
+    if plain.trim_end() == key {
+        key.to_owned()
+    } else {
+        serde_json::to_string(key).unwrap_or_default()
+    }
+}
+
+/// Formatting of an existing entry that a replacement should carry over.
+#[derive(Debug, Default)]
+struct EntryStyle {
+    /// Quote character of an inline string value.
+    quote: Option<char>,
+    /// Inline comment after the value, including its leading whitespace.
+    comment: Option<String>,
+    /// Leading text of block list items, such as `  - `.
+    item_prefix: Option<String>,
+}
+
+impl EntryStyle {
+    fn of(entry: &[String], key_end: usize) -> Self {
+        let inline = entry[0][key_end..].trim_start().trim_start_matches(':');
+        let comment_at = comment_start(inline);
+        let value = inline[..comment_at].trim();
+        let item_prefix = entry[1..].iter().find_map(|line| {
+            let dash = line
+                .find("- ")
+                .or_else(|| (line.trim() == "-").then_some(0))?;
+            line[..dash]
+                .trim()
+                .is_empty()
+                .then(|| format!("{}- ", &line[..dash]))
+        });
+        Self {
+            quote: value.chars().next().filter(|c| matches!(c, '"' | '\'')),
+            comment: (comment_at < inline.len()).then(|| {
+                let before = inline[..comment_at].trim_end_matches([' ', '\t']);
+                inline[before.len()..].to_owned()
+            }),
+            item_prefix,
+        }
+    }
+}
+
+/// Byte offset of a ` #` comment in an inline value, or its length when there is none.
+fn comment_start(value: &str) -> usize {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') if prev == ' ' || prev == '\t' => return i,
+            _ => {}
+        }
+        prev = c;
+    }
+    value.len()
+}
+
+fn render_entry(key: &str, value: &Value, style: &EntryStyle) -> Vec<String> {
+    let comment = style.comment.as_deref().unwrap_or("");
+    match value {
+        Value::Sequence(items) if !items.is_empty() => {
+            let prefix = style.item_prefix.as_deref().unwrap_or("  - ");
+            std::iter::once(format!("{key}:{comment}"))
+                .chain(
+                    items
+                        .iter()
+                        .map(|item| format!("{prefix}{}", render_scalar(item, None))),
+                )
+                .collect()
+        }
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            let yaml = serde_yaml::to_string(mapping).unwrap_or_default();
+            std::iter::once(format!("{key}:{comment}"))
+                .chain(yaml.lines().map(|line| format!("  {line}")))
+                .collect()
+        }
+        Value::Null => vec![format!("{key}:{comment}")],
+        scalar => vec![format!(
+            "{key}: {}{comment}",
+            render_scalar(scalar, style.quote)
+        )],
+    }
+}
+
+/// Render a value for a single line: strings in the requested quote style (double quotes
+/// when YAML needs any), nested collections in flow style.
+fn render_scalar(value: &Value, quote: Option<char>) -> String {
+    match value {
+        Value::String(s) => {
+            let plain = serde_yaml::to_string(s).unwrap_or_default();
+            match quote {
+                Some('\'') if !s.contains('\n') => format!("'{}'", s.replace('\'', "''")),
+                None if plain.trim_end() == s => s.clone(),
+                _ => serde_json::to_string(s).unwrap_or_default(),
+            }
+        }
+        Value::Sequence(_) | Value::Mapping(_) | Value::Tagged(_) => {
+            serde_json::to_string(value).unwrap_or_default()
+        }
+        scalar => serde_yaml::to_string(scalar)
+            .unwrap_or_default()
+            .trim_end()
+            .to_owned(),
+    }
+}
+
+/// Append to a single-line flow list such as `tags: [a, b]  # note`.
+fn append_flow(line: &str, key_end: usize, value: &Value) -> Option<String> {
+    let after_key = &line[key_end..];
+    let value_start = key_end + after_key.find(':')? + 1;
+    let inline = &line[value_start..];
+    let comment_at = comment_start(inline);
+    let flow = inline[..comment_at].trim_end();
+    let close = value_start + flow.len() - 1;
+    if !flow.trim_start().starts_with('[') || !flow.ends_with(']') {
+        return None;
+    }
+    let empty = flow.trim_start()[1..flow.trim_start().len() - 1]
+        .trim()
+        .is_empty();
+    let separator = if empty { "" } else { ", " };
+    Some(format!(
+        "{}{separator}{}{}",
+        &line[..close],
+        render_scalar(value, None),
+        &line[close..]
+    ))
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+const NOTE: &str = "---
+title: \"Project A\"   # display name
+project.status: active
+tags:
+  - type/project
+  - status/open
+rating: 4
+
+# planning
+due: 2026-03-01
+---
+# Project A
+
+Body with trailing spaces
+and no final newline";
+
+fn edit(content: &str, f: impl FnOnce(&mut Frontmatter)) -> String {
+    let mut fm = Frontmatter::parse(content).unwrap();
+    f(&mut fm);
+    fm.render()
+}
+
+#[test]
+fn round_trips_untouched_notes() {
+    assert_eq!(edit(NOTE, |_| {}), NOTE);
+    let crlf = NOTE.replace('\n', "\r\n");
+    assert_eq!(edit(&crlf, |_| {}), crlf);
+    assert_eq!(edit("# No frontmatter\n", |_| {}), "# No frontmatter\n");
+}
+
+#[test]
+fn reads_values_and_keys() {
+    let fm = Frontmatter::parse(NOTE).unwrap();
+    assert_eq!(fm.get("title"), Some(Value::from("Project A")));
+    assert_eq!(fm.get("project.status"), Some(Value::from("active")));
+    assert_eq!(
+        fm.get("tags"),
+        Some(Value::Sequence(vec![
+            "type/project".into(),
+            "status/open".into()
+        ]))
+    );
+    assert_eq!(fm.get("missing"), None);
+    assert_eq!(
+        fm.keys(),
+        ["title", "project.status", "tags", "rating", "due"]
+    );
+}
+
+#[test]
+fn set_replaces_in_place_keeping_quotes_and_comments() {
+    let out = edit(NOTE, |fm| {
+        fm.set("title", &Value::from("Project B"));
+        fm.set("project.status", &Value::from("done"));
+    });
+    assert_eq!(
+        out,
+        NOTE.replace("\"Project A\"   #", "\"Project B\"   #")
+            .replace("status: active", "status: done")
+    );
+}
+
+#[test]
+fn set_adds_new_keys_and_lists() {
+    let out = edit(NOTE, |fm| {
+        fm.set("related", &Value::Sequence(vec!["[[Project B]]".into()]));
+        fm.set("rating", &Value::from(5));
+    });
+    assert!(out.contains("rating: 5\n\n# planning\ndue: 2026-03-01\nrelated:\n  - \"[[Project B]]\"\n---\n# Project A"));
+}
+
+#[test]
+fn set_creates_frontmatter_when_missing() {
+    let out = edit("# Title\n", |fm| fm.set("status", &Value::from("draft")));
+    assert_eq!(out, "---\nstatus: draft\n---\n# Title\n");
+}
+
+#[test]
+fn remove_drops_nested_lines_only() {
+    let out = edit(NOTE, |fm| {
+        assert!(fm.remove("tags"));
+        assert!(!fm.remove("missing"));
+    });
+    assert_eq!(
+        out,
+        NOTE.replace("tags:\n  - type/project\n  - status/open\n", "")
+    );
+}
+
+#[test]
+fn rename_keeps_value_and_position() {
+    let out = edit(NOTE, |fm| {
+        assert!(fm.rename("rating", "score").unwrap());
+        assert!(!fm.rename("missing", "other").unwrap());
+        assert!(fm.rename("title", "due").is_err());
+    });
+    assert_eq!(out, NOTE.replace("rating: 4", "score: 4"));
+}
+
+#[test]
+fn append_keeps_list_style() {
+    let out = edit(NOTE, |fm| {
+        assert!(fm.append("tags", &Value::from("topic/rust")));
+        assert!(!fm.append("tags", &Value::from("type/project")));
+    });
+    assert_eq!(
+        out,
+        NOTE.replace("  - status/open\n", "  - status/open\n  - topic/rust\n")
+    );
+
+    let flow = "---\naliases: [A, \"B\"]  # short names\n---\n";
+    let out = edit(flow, |fm| {
+        fm.append("aliases", &Value::from("C"));
+    });
+    assert_eq!(out, "---\naliases: [A, \"B\", C]  # short names\n---\n");
+}
+
+#[test]
+fn append_promotes_scalars_and_creates_lists() {
+    let out = edit("---\nauthor: Ann\n---\n", |fm| {
+        fm.append("author", &Value::from("Bob"));
+        fm.append("editors", &Value::from("Cy"));
+    });
+    assert_eq!(
+        out,
+        "---\nauthor:\n  - Ann\n  - Bob\neditors:\n  - Cy\n---\n"
+    );
+}
+
+#[test]
+fn append_keeps_the_key_line_comment() {
+    let out = edit("---\ntags: # none yet\nauthor: Ann  # lead\n---\n", |fm| {
+        fm.append("tags", &Value::from("topic/rust"));
+        fm.append("author", &Value::from("Bob"));
+    });
+    assert_eq!(
+        out,
+        "---\ntags: # none yet\n  - topic/rust\nauthor:  # lead\n  - Ann\n  - Bob\n---\n"
+    );
+}
+
+#[test]
+fn rejects_malformed_frontmatter() {
+    assert!(Frontmatter::parse("---\ntitle: x\n").is_err());
+    assert!(Frontmatter::parse("---\n- a\n- b\n---\n").is_err());
+    assert!(Frontmatter::parse("---\n---\nBody").is_ok());
+}
+
+#[test]
+fn flatten_replaces_nested_objects_in_place() {
+    let content = "---\ntitle: A\nproject:\n  status: active\n  meta: {owner: \"[[Ann]]\", since: 2026}\ntags: [x]\n---\nBody\n";
+    let out = edit(content, |fm| {
+        assert!(fm.flatten("project"));
+        assert!(!fm.flatten("title"));
+    });
+    assert_eq!(
+        out,
+        "---\ntitle: A\nproject.status: active\nproject.meta.owner: \"[[Ann]]\"\nproject.meta.since: 2026\ntags: [x]\n---\nBody\n"
+    );
+
+    let conflict = "---\nproject: {status: a}\nproject.status: b\n---\n";
+    assert_eq!(
+        edit(conflict, |fm| assert!(!fm.flatten("project"))),
+        conflict
+    );
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests;
+
+use super::Structure;
+use regex::Regex;
+use serde_yaml::Value;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `Key:: value` filling a line, optionally inside a list item, task or quote.
+static LINE_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:(?:[-*+]|\d+[.)])\s+(?:\[.\]\s+)?|>\s*)*([\p{L}\p{N}_][\p{L}\p{N}_ /-]*?)::(?:\s+(.*))?$").unwrap()
+});
+/// `[key:: value]` or `(key:: value)` anywhere in a line; values may hold `[[links]]`.
+static BRACKETED_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\[([\p{L}\p{N}_][\p{L}\p{N}_ /-]*?)::\s*((?:\[\[[^\]]*\]\]|[^\[\]])*)\]|\(([\p{L}\p{N}_][\p{L}\p{N}_ /-]*?)::\s*((?:\[\[[^\]]*\]\]|[^()])*)\)",
+    )
+    .unwrap()
+});
+static INLINE_CODE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`]*`").unwrap());
+
+/// Dataview inline fields in a note body, outside frontmatter, code blocks and inline code.
+///
+/// Numbers and booleans are typed, everything else (dates and `[[links]]` included) stays a
+/// string, and a key given more than once collects its values in a list. As in Dataview, a
+/// key with capitals or spaces is also available in its sanitized form: `Due Date` as
+/// `due-date`.
+pub fn parse_inline_fields(content: &str) -> HashMap<String, Value> {
+    let structure = Structure::parse(content);
+    let (_, body) = super::parse_frontmatter(content);
+    let first_body_line = content[..content.len() - body.len()].matches('\n').count() + 1;
+
+    let mut fields = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let number = i + 1;
+        if number < first_body_line
+            || structure
+                .code_blocks
+                .iter()
+                .any(|c| (c.line..=c.end).contains(&number))
+        {
+            continue;
+        }
+        let line = INLINE_CODE_RE.replace_all(line, "");
+
+        let mut found = false;
+        for caps in BRACKETED_FIELD_RE.captures_iter(&line) {
+            let (key, value) = match (caps.get(1), caps.get(3)) {
+                (Some(key), _) => (key, caps.get(2)),
+                (None, Some(key)) => (key, caps.get(4)),
+                (None, None) => continue,
+            };
+            insert(&mut fields, key.as_str(), value.map_or("", |m| m.as_str()));
+            found = true;
+        }
+        if !found {
+            if let Some(caps) = LINE_FIELD_RE.captures(&line) {
+                insert(
+                    &mut fields,
+                    &caps[1],
+                    caps.get(2).map_or("", |m| m.as_str()),
+                );
+            }
+        }
+    }
+    fields
+}
+
+fn insert(fields: &mut HashMap<String, Value>, key: &str, raw: &str) {
+    let key = key.trim();
+    let value = typed(raw);
+    let sanitized = key.to_lowercase().replace(' ', "-");
+    if sanitized != key {
+        push(fields, sanitized, value.clone());
+    }
+    push(fields, key.to_owned(), value);
+}
+
+/// Store a value, turning a repeated key into a list.
+fn push(fields: &mut HashMap<String, Value>, key: String, value: Value) {
+    match fields.entry(key) {
+        Entry::Occupied(mut entry) => match entry.get_mut() {
+            Value::Sequence(items) => items.push(value),
+            existing => *existing = Value::Sequence(vec![existing.clone(), value]),
+        },
+        Entry::Vacant(entry) => {
+            entry.insert(value);
+        }
+    }
+}
+
+fn typed(raw: &str) -> Value {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Value::Null;
+    }
+    match serde_yaml::from_str::<Value>(raw) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) => value,
+        _ => Value::String(raw.to_owned()),
+    }
+}
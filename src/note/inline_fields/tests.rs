@@ -0,0 +1,56 @@
+use super::*;
+
+const NOTE: &str = "---
+rating: 5
+---
+# Book
+
+rating:: 4
+Due Date:: 2025-05-01
+- author:: [[Robert C. Martin]]
+- [ ] Finish reading [due:: 2025-06-01] (priority:: high)
+> finished:: true
+Read with [related:: [[Clean Architecture]]] and [related:: [[Refactoring]]].
+empty::
+Not a field: `code:: 1` or a::b
+
+```
+hidden:: 1
+```
+";
+
+#[test]
+fn parses_line_and_bracketed_fields() {
+    let fields = parse_inline_fields(NOTE);
+    assert_eq!(fields.get("rating"), Some(&Value::from(4)));
+    assert_eq!(
+        fields.get("author"),
+        Some(&Value::from("[[Robert C. Martin]]"))
+    );
+    assert_eq!(fields.get("due"), Some(&Value::from("2025-06-01")));
+    assert_eq!(fields.get("priority"), Some(&Value::from("high")));
+    assert_eq!(fields.get("finished"), Some(&Value::Bool(true)));
+    assert_eq!(fields.get("empty"), Some(&Value::Null));
+}
+
+#[test]
+fn repeated_keys_collect_and_names_are_sanitized() {
+    let fields = parse_inline_fields(NOTE);
+    assert_eq!(
+        fields.get("related"),
+        Some(&Value::Sequence(vec![
+            "[[Clean Architecture]]".into(),
+            "[[Refactoring]]".into()
+        ]))
+    );
+    assert_eq!(fields.get("Due Date"), Some(&Value::from("2025-05-01")));
+    assert_eq!(fields.get("due-date"), Some(&Value::from("2025-05-01")));
+}
+
+#[test]
+fn skips_frontmatter_and_code() {
+    let fields = parse_inline_fields(NOTE);
+    for key in ["code", "hidden", "a", "Not a field"] {
+        assert!(!fields.contains_key(key), "{key}");
+    }
+}
@@ -1,18 +1,31 @@
+mod frontmatter;
+mod inline_fields;
+mod structure;
+mod tasks;
 #[cfg(test)]
 mod tests;
 
 use regex::Regex;
 use serde_yaml::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
 use walkdir::WalkDir;
 
+pub use frontmatter::Frontmatter;
+pub use inline_fields::parse_inline_fields;
+pub use structure::{BlockId, Callout, CodeBlock, Heading, Section, Structure};
+pub use tasks::{parse_tasks, Task};
+
 static WIKILINK_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]").unwrap());
 
 /// A vault note enriched with file metadata and parsed frontmatter.
+///
+/// Build one with [`NoteContext::from_file`] or [`NoteContext::from_content`]; the body is
+/// read through [`NoteContext::content`] and replaced with [`NoteContext::set_content`], so
+/// everything parsed from it stays in step.
 #[derive(Debug, Clone)]
 pub struct NoteContext {
     pub path: PathBuf,
@@ -23,7 +36,10 @@ pub struct NoteContext {
     pub tags: Vec<String>,
     pub links: Vec<String>,
     pub properties: HashMap<String, Value>,
-    pub content: String,
+    /// Dataview inline fields from the body (`rating:: 4`, `[due:: 2025-05-01]`), kept apart
+    /// from the frontmatter `properties` and parsed on first use by [`Self::inline_fields`].
+    inline_cache: OnceLock<HashMap<String, Value>>,
+    content: String,
 }
 
 impl NoteContext {
@@ -31,41 +47,56 @@ impl NoteContext {
     pub fn from_file(vault_root: &Path, abs_path: &Path) -> Option<Self> {
         let content = fs::read_to_string(abs_path).ok()?;
         let rel_path = abs_path.strip_prefix(vault_root).ok()?;
-
-        let name = abs_path
-            .file_stem()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .into_owned();
-        let ext = abs_path
-            .extension()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .into_owned();
-        let folder = rel_path
-            .parent()
-            .unwrap_or(Path::new(""))
-            .to_string_lossy()
-            .into_owned();
-
-        let (properties, body) = parse_frontmatter(&content);
-
-        let tags = extract_tags(&properties);
-        let links = extract_wikilinks(body);
-
         Some(Self {
             path: abs_path.to_path_buf(),
-            rel_path: rel_path.to_string_lossy().into_owned(),
-            name,
-            ext,
-            folder,
-            tags,
-            links,
-            properties,
-            content,
+            ..Self::from_content(&rel_path.to_string_lossy(), content)
         })
     }
 
+    /// Build a note that is not read from disk, e.g. from an editor buffer or in tests.
+    ///
+    /// Name, extension and folder come from the vault-relative `rel_path`, which also
+    /// serves as `path`; frontmatter, tags and links are parsed from `content`.
+    pub fn from_content(rel_path: &str, content: impl Into<String>) -> Self {
+        let path = Path::new(rel_path);
+        let os_str =
+            |s: Option<&std::ffi::OsStr>| s.unwrap_or_default().to_string_lossy().into_owned();
+        let mut note = Self {
+            path: path.to_path_buf(),
+            rel_path: rel_path.to_owned(),
+            name: os_str(path.file_stem()),
+            ext: os_str(path.extension()),
+            folder: path
+                .parent()
+                .unwrap_or(Path::new(""))
+                .to_string_lossy()
+                .into_owned(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            properties: HashMap::new(),
+            inline_cache: OnceLock::new(),
+            content: String::new(),
+        };
+        note.set_content(content);
+        note
+    }
+
+    /// The raw note text, frontmatter included.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Replace the note text, re-parsing frontmatter, tags and links and dropping any
+    /// inline fields parsed from the old text.
+    pub fn set_content(&mut self, content: impl Into<String>) {
+        self.content = content.into();
+        let (properties, body) = parse_frontmatter(&self.content);
+        self.tags = extract_tags(&properties);
+        self.links = extract_wikilinks(body);
+        self.properties = properties;
+        self.inline_cache = OnceLock::new();
+    }
+
     /// Get a frontmatter property value by key.
     pub fn get_property(&self, key: &str) -> Option<&Value> {
         self.properties.get(key)
@@ -78,6 +109,33 @@ impl NoteContext {
             .any(|t| t == tag || t.starts_with(&format!("{tag}/")))
     }
 
+    /// Markdown structure of the note body: headings, block IDs, callouts and code blocks.
+    pub fn structure(&self) -> Structure {
+        Structure::parse(&self.content)
+    }
+
+    /// ATX headings with their line numbers.
+    pub fn headings(&self) -> Vec<Heading> {
+        self.structure().headings
+    }
+
+    /// The section under the first heading named `name` (case-insensitive), as in
+    /// `[[Note#Work log]]`.
+    pub fn section(&self, name: &str) -> Option<Section> {
+        self.structure().section(&self.content, name)
+    }
+
+    /// Dataview inline fields in the body, parsed once.
+    pub fn inline_fields(&self) -> &HashMap<String, Value> {
+        self.inline_cache
+            .get_or_init(|| parse_inline_fields(&self.content))
+    }
+
+    /// Checkbox tasks in the note, with dates, nesting and section.
+    pub fn tasks(&self) -> Vec<Task> {
+        parse_tasks(&self.content)
+    }
+
     /// Check if the note links to a given target (by name, case-insensitive).
     pub fn has_link(&self, target: &str) -> bool {
         let target_lower = target.to_lowercase();
@@ -91,28 +149,105 @@ pub fn walk_vault(vault_root: &Path) -> Vec<NoteContext> {
     let vault_root = vault_root
         .canonicalize()
         .unwrap_or_else(|_| vault_root.to_path_buf());
-    let mut notes = Vec::new();
-    for entry in WalkDir::new(&vault_root)
+    note_paths(&vault_root)
+        .iter()
+        .filter_map(|path| NoteContext::from_file(&vault_root, path))
+        .collect()
+}
+
+/// Canonical paths of the `.md` files under `dir`, skipping hidden directories.
+fn note_paths(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
         .into_iter()
         .filter_entry(|e| {
             // Skip hidden directories (like .obsidian, .trash) but not the root
             e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.')
         })
         .filter_map(Result::ok)
+        .map(|entry| {
+            let path = entry.into_path();
+            path.canonicalize().unwrap_or(path)
+        })
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "md"))
+        .collect()
+}
+
+/// Bring `notes` up to date after `path` changed on disk.
+///
+/// Handles creates, edits, deletes and renames of single notes as well as whole folders:
+/// an existing `.md` file is (re)loaded, an existing directory is rescanned so the notes
+/// below it match the disk (new ones loaded, edited ones reloaded, vanished ones dropped),
+/// and a missing path drops every note at or below it. Paths in hidden folders are
+/// ignored, as in `walk_vault`. Returns whether `notes` changed.
+pub fn refresh_path(notes: &mut Vec<NoteContext>, vault_root: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(vault_root) else {
+        return false;
+    };
+    if rel
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
     {
-        let path = entry.into_path();
-        let path = path.canonicalize().unwrap_or(path);
-        if path.is_file() && path.extension().is_some_and(|e| e == "md") {
-            if let Some(ctx) = NoteContext::from_file(&vault_root, &path) {
+        return false;
+    }
+
+    if path.is_dir() {
+        let on_disk: HashSet<PathBuf> = note_paths(path).into_iter().collect();
+        let before = notes.len();
+        notes.retain(|n| !n.path.starts_with(path) || on_disk.contains(&n.path));
+        let mut changed = notes.len() != before;
+        for note_path in &on_disk {
+            changed |= refresh_path(notes, vault_root, note_path);
+        }
+        return changed;
+    }
+
+    if path.is_file() {
+        if path.extension().is_none_or(|e| e != "md") {
+            return false;
+        }
+        let Some(ctx) = NoteContext::from_file(vault_root, path) else {
+            return false;
+        };
+        return match notes.iter_mut().find(|n| n.path == path) {
+            Some(existing) if existing.content == ctx.content => false,
+            Some(existing) => {
+                *existing = ctx;
+                true
+            }
+            None => {
                 notes.push(ctx);
+                true
             }
-        }
+        };
     }
-    notes
+
+    // Deleted or renamed away: drop the note, or everything under a removed folder
+    let before = notes.len();
+    notes.retain(|n| !n.path.starts_with(path));
+    notes.len() != before
+}
+
+/// ATX headings of a note, skipping frontmatter and fenced code blocks.
+pub fn parse_headings(content: &str) -> Vec<Heading> {
+    Structure::parse(content).headings
+}
+
+/// Line of a top-level frontmatter key in the raw note, or 1 when it cannot be located.
+pub(crate) fn frontmatter_line(content: &str, key: &str) -> usize {
+    content
+        .lines()
+        .enumerate()
+        .skip(1)
+        .take_while(|(_, line)| line.trim_end() != "---")
+        .find(|(_, line)| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map_or(1, |(i, _)| i + 1)
 }
 
 /// Split frontmatter from content. Returns (properties, body).
-fn parse_frontmatter(content: &str) -> (HashMap<String, Value>, &str) {
+pub(crate) fn parse_frontmatter(content: &str) -> (HashMap<String, Value>, &str) {
     let empty = (HashMap::new(), content);
 
     if !content.starts_with("---") {
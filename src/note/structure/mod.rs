@@ -0,0 +1,230 @@
+#[cfg(test)]
+mod tests;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+static BLOCK_ID_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)\^([A-Za-z0-9-]+)\s*$").unwrap());
+static CALLOUT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[!([A-Za-z0-9_-]+)\]([+-]?)\s*(.*)$").unwrap());
+
+/// An ATX heading (`## Title`) with its 1-based line in the note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: usize,
+    pub text: String,
+    pub line: usize,
+}
+
+/// A heading and everything under it, up to the next heading of the same or a higher level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub heading: Heading,
+    /// Last line of the section (inclusive); equals `heading.line` for an empty last section.
+    pub end: usize,
+    /// The lines after the heading, subsections included, joined with `\n`.
+    pub text: String,
+}
+
+/// A block reference target: `Some paragraph ^abc123`, or `^abc123` on its own line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockId {
+    pub id: String,
+    pub line: usize,
+}
+
+/// A `> [!type] Title` callout spanning `line..=end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Callout {
+    /// Lowercased callout type (`note`, `warning`, ...).
+    pub kind: String,
+    pub title: String,
+    /// `Some(true)` for `[!x]+`, `Some(false)` for `[!x]-`, `None` when not foldable.
+    pub expanded: Option<bool>,
+    pub line: usize,
+    pub end: usize,
+}
+
+/// A fenced code block from its opening to its closing fence (or the end of the note).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Info string after the fence, e.g. `dataview`; empty when absent.
+    pub lang: String,
+    pub line: usize,
+    pub end: usize,
+}
+
+/// Markdown structure of a note body. All line numbers are 1-based lines of the file,
+/// frontmatter included, so they can be reported and edited directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Structure {
+    pub headings: Vec<Heading>,
+    pub block_ids: Vec<BlockId>,
+    pub callouts: Vec<Callout>,
+    pub code_blocks: Vec<CodeBlock>,
+}
+
+/// An open fence: its marker character, length and the block being built.
+struct Fence {
+    marker: char,
+    len: usize,
+    block: CodeBlock,
+}
+
+impl Structure {
+    /// Scan a note once. Headings, block IDs and callouts inside code blocks are ignored.
+    pub fn parse(content: &str) -> Self {
+        let (_, body) = super::parse_frontmatter(content);
+        let first_line = content[..content.len() - body.len()].matches('\n').count() + 1;
+        let mut structure = Self::default();
+
+        let mut fence: Option<Fence> = None;
+        let mut callout: Option<Callout> = None;
+        for (i, line) in body.lines().enumerate() {
+            let number = first_line + i;
+            let trimmed = line.trim_start_matches(' ');
+            let indented = line.len() - trimmed.len() > 3;
+
+            if let Some(open) = &mut fence {
+                let run = trimmed.chars().take_while(|&c| c == open.marker).count();
+                if !indented && run >= open.len && trimmed[run..].trim().is_empty() {
+                    open.block.end = number;
+                    structure.code_blocks.push(fence.take().unwrap().block);
+                }
+                continue;
+            }
+
+            if let Some(open) = &mut callout {
+                if trimmed.starts_with('>') {
+                    open.end = number;
+                } else {
+                    structure.callouts.extend(callout.take());
+                }
+            }
+            if indented {
+                continue;
+            }
+
+            if let Some(opened) = open_fence(trimmed, number) {
+                fence = Some(opened);
+                continue;
+            }
+            if let Some(heading) = heading(trimmed, number) {
+                structure.headings.push(heading);
+            } else if callout.is_none() {
+                callout = open_callout(trimmed, number);
+            }
+            if let Some(caps) = BLOCK_ID_RE.captures(line) {
+                structure.block_ids.push(BlockId {
+                    id: caps[1].to_owned(),
+                    line: number,
+                });
+            }
+        }
+
+        // An unclosed fence runs to the end of the note, as Obsidian renders it
+        if let Some(open) = fence {
+            structure.code_blocks.push(CodeBlock {
+                end: content.lines().count().max(open.block.line),
+                ..open.block
+            });
+        }
+        structure.callouts.extend(callout);
+        structure
+    }
+
+    /// Every heading with its section, in note order. `content` is the note this was parsed from.
+    pub fn sections(&self, content: &str) -> Vec<Section> {
+        let lines: Vec<&str> = content.lines().collect();
+        self.headings
+            .iter()
+            .enumerate()
+            .map(|(i, heading)| {
+                let end = self.headings[i + 1..]
+                    .iter()
+                    .find(|next| next.level <= heading.level)
+                    .map_or(lines.len(), |next| next.line - 1)
+                    .max(heading.line);
+                Section {
+                    heading: heading.clone(),
+                    end,
+                    text: lines[heading.line..end].join("\n"),
+                }
+            })
+            .collect()
+    }
+
+    /// The first section whose heading text matches `name`, ignoring case.
+    pub fn section(&self, content: &str, name: &str) -> Option<Section> {
+        let name = name.trim().to_lowercase();
+        self.sections(content)
+            .into_iter()
+            .find(|s| s.heading.text.to_lowercase() == name)
+    }
+}
+
+fn open_fence(trimmed: &str, line: usize) -> Option<Fence> {
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.chars().take_while(|&c| c == marker).count();
+    let info = trimmed[len..].trim();
+    // A backtick fence's info string cannot itself contain backticks
+    if len < 3 || (marker == '`' && info.contains('`')) {
+        return None;
+    }
+    Some(Fence {
+        marker,
+        len,
+        block: CodeBlock {
+            lang: info
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+            line,
+            end: line,
+        },
+    })
+}
+
+fn heading(trimmed: &str, line: usize) -> Option<Heading> {
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    Some(Heading {
+        level,
+        text: strip_closing_hashes(rest.trim()).to_owned(),
+        line,
+    })
+}
+
+/// Drop an optional closing `#` run, which only counts when it is the whole text or
+/// follows whitespace: `## Title ##` is "Title" but `## Learning C#` keeps its `#`.
+fn strip_closing_hashes(text: &str) -> &str {
+    let body = text.trim_end_matches('#');
+    if body.is_empty() {
+        ""
+    } else if body.ends_with([' ', '\t']) {
+        body.trim_end()
+    } else {
+        text
+    }
+}
+
+fn open_callout(trimmed: &str, line: usize) -> Option<Callout> {
+    let inner = trimmed.strip_prefix('>')?.trim_start();
+    let caps = CALLOUT_RE.captures(inner)?;
+    Some(Callout {
+        kind: caps[1].to_lowercase(),
+        title: caps[3].trim().to_owned(),
+        expanded: match &caps[2] {
+            "+" => Some(true),
+            "-" => Some(false),
+            _ => None,
+        },
+        line,
+        end: line,
+    })
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+const NOTE: &str = "---
+title: Alpha
+---
+# Alpha
+
+Summary paragraph ^summary
+
+## Backlog
+
+- [ ] Ship it ^task-1
+
+### Someday
+
+- [ ] Rewrite
+
+## Work log
+
+> [!note]+ Kickoff
+> Agreed scope
+> ^kickoff
+
+````md
+## Not a heading ^nope
+```
+> [!tip] nor a callout
+````
+
+~~~
+unclosed";
+
+#[test]
+fn finds_headings_outside_code() {
+    let structure = Structure::parse(NOTE);
+    assert_eq!(
+        structure
+            .headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str(), h.line))
+            .collect::<Vec<_>>(),
+        [
+            (1, "Alpha", 4),
+            (2, "Backlog", 8),
+            (3, "Someday", 12),
+            (2, "Work log", 16)
+        ]
+    );
+}
+
+#[test]
+fn sections_include_subsections() {
+    let structure = Structure::parse(NOTE);
+    let backlog = structure.section(NOTE, "backlog").unwrap();
+    assert_eq!((backlog.heading.line, backlog.end), (8, 15));
+    assert_eq!(
+        backlog.text,
+        "\n- [ ] Ship it ^task-1\n\n### Someday\n\n- [ ] Rewrite\n"
+    );
+
+    let alpha = structure.section(NOTE, "Alpha").unwrap();
+    assert_eq!(alpha.end, 29);
+    assert_eq!(structure.section(NOTE, "Missing"), None);
+}
+
+#[test]
+fn finds_block_ids_callouts_and_code_blocks() {
+    let structure = Structure::parse(NOTE);
+    assert_eq!(
+        structure
+            .block_ids
+            .iter()
+            .map(|b| (b.id.as_str(), b.line))
+            .collect::<Vec<_>>(),
+        [("summary", 6), ("task-1", 10), ("kickoff", 20)]
+    );
+    assert_eq!(
+        structure.callouts,
+        [Callout {
+            kind: "note".to_owned(),
+            title: "Kickoff".to_owned(),
+            expanded: Some(true),
+            line: 18,
+            end: 20,
+        }]
+    );
+    assert_eq!(
+        structure
+            .code_blocks
+            .iter()
+            .map(|c| (c.lang.as_str(), c.line, c.end))
+            .collect::<Vec<_>>(),
+        [("md", 22, 26), ("", 28, 29)]
+    );
+}
+
+#[test]
+fn empty_trailing_section_ends_at_its_heading() {
+    let content = "# Title\n\n## Log";
+    let log = Structure::parse(content).section(content, "Log").unwrap();
+    assert_eq!((log.heading.line, log.end, log.text.as_str()), (3, 3, ""));
+}
+
+#[test]
+fn closing_hashes_need_whitespace_before_them() {
+    let content = "## Learning C#\n\n## Title ##\n\n## #\n\n### Sharp #C# #";
+    let texts: Vec<_> = Structure::parse(content)
+        .headings
+        .into_iter()
+        .map(|h| h.text)
+        .collect();
+    assert_eq!(texts, ["Learning C#", "Title", "", "Sharp #C#"]);
+    assert!(Structure::parse(content)
+        .section(content, "Learning C#")
+        .is_some());
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests;
+
+use super::Structure;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static TASK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([ \t]*)(?:[-*+]|\d+[.)])[ \t]+\[(.)\](?:[ \t]+(.*))?$").unwrap()
+});
+static EMOJI_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\s*(📅|🗓️?|⏳|⌛|🛫|✅|➕)\u{FE0F}?\s*(\d{4}-\d{2}-\d{2})").unwrap()
+});
+static INLINE_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\s*[\[(](due|scheduled|start|completion|created)::\s*([^\])]*)[\])]").unwrap()
+});
+
+/// A checkbox list item: `- [ ] Ship it 📅 2026-03-01`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    /// The character between the brackets: ` ` open, `x` done, `-` cancelled, `/` in progress.
+    pub status: char,
+    /// Task text with date markers removed.
+    pub text: String,
+    /// 1-based line in the note.
+    pub line: usize,
+    /// Leading whitespace width, tabs counted as 4.
+    pub indent: usize,
+    /// Index of the enclosing task in the note's task list, for nested subtasks.
+    pub parent: Option<usize>,
+    /// Text of the nearest heading above the task.
+    pub section: Option<String>,
+    pub due: Option<String>,
+    pub scheduled: Option<String>,
+    pub start: Option<String>,
+    /// Date the task was completed (`✅` or `[completion:: ]`).
+    pub completion: Option<String>,
+    pub created: Option<String>,
+}
+
+impl Task {
+    /// Done or cancelled; any other status, custom ones included, counts as open.
+    pub fn is_closed(&self) -> bool {
+        matches!(self.status, 'x' | 'X' | '-')
+    }
+}
+
+/// Every task in a note, outside code blocks, in line order.
+///
+/// Dates are read from Tasks-plugin emoji (`📅 ⏳ 🛫 ✅ ➕`) and Dataview inline fields
+/// (`[due:: 2026-03-01]`, `(scheduled:: ...)`).
+pub fn parse_tasks(content: &str) -> Vec<Task> {
+    let structure = Structure::parse(content);
+    let first_body_line = {
+        let (_, body) = super::parse_frontmatter(content);
+        content[..content.len() - body.len()].matches('\n').count() + 1
+    };
+
+    let mut tasks: Vec<Task> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let number = i + 1;
+        let in_code = structure
+            .code_blocks
+            .iter()
+            .any(|c| (c.line..=c.end).contains(&number));
+        if number < first_body_line || in_code {
+            continue;
+        }
+        let Some(caps) = TASK_RE.captures(line) else {
+            continue;
+        };
+
+        let indent = caps[1].chars().map(|c| if c == '\t' { 4 } else { 1 }).sum();
+        let section = structure
+            .headings
+            .iter()
+            .rev()
+            .find(|h| h.line < number)
+            .map(|h| h.text.clone());
+        // The nearest shallower task above, unless a heading separates the two lists
+        let parent = tasks
+            .iter()
+            .rposition(|t| t.indent < indent)
+            .filter(|&p| tasks[p].section == section);
+
+        let mut task = Task {
+            status: caps[2].chars().next().unwrap_or(' '),
+            text: String::new(),
+            line: number,
+            indent,
+            parent,
+            section,
+            due: None,
+            scheduled: None,
+            start: None,
+            completion: None,
+            created: None,
+        };
+        task.text = read_dates(caps.get(3).map_or("", |m| m.as_str()), &mut task);
+        tasks.push(task);
+    }
+    tasks
+}
+
+/// Fill the task's dates from its text and return the text without them.
+fn read_dates(text: &str, task: &mut Task) -> String {
+    for caps in EMOJI_DATE_RE.captures_iter(text) {
+        let slot = match &caps[1] {
+            "📅" | "🗓" | "🗓️" => &mut task.due,
+            "⏳" | "⌛" => &mut task.scheduled,
+            "🛫" => &mut task.start,
+            "✅" => &mut task.completion,
+            _ => &mut task.created,
+        };
+        slot.get_or_insert_with(|| caps[2].to_owned());
+    }
+    for caps in INLINE_FIELD_RE.captures_iter(text) {
+        let slot = match &caps[1] {
+            "due" => &mut task.due,
+            "scheduled" => &mut task.scheduled,
+            "start" => &mut task.start,
+            "completion" => &mut task.completion,
+            _ => &mut task.created,
+        };
+        slot.get_or_insert_with(|| caps[2].trim().to_owned());
+    }
+    let text = EMOJI_DATE_RE.replace_all(text, "");
+    INLINE_FIELD_RE.replace_all(&text, "").trim().to_owned()
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+const NOTE: &str = "---
+status: active
+---
+# Backlog
+
+- [ ] Ship the release 📅 2026-03-01 ⏳ 2026-02-20
+    - [x] Write changelog ✅ 2026-02-18
+    - [/] Tag the build [due:: 2026-02-25]
+- [-] Drop IE support
+1. [ ] Numbered task (scheduled:: 2026-04-01)
+
+```md
+- [ ] Example in a code block
+```
+
+## Someday
+
+\t- [?] Custom status
+- Not a task [ ]
+";
+
+#[test]
+fn parses_status_text_and_lines() {
+    let tasks = parse_tasks(NOTE);
+    assert_eq!(
+        tasks
+            .iter()
+            .map(|t| (t.status, t.text.as_str(), t.line))
+            .collect::<Vec<_>>(),
+        [
+            (' ', "Ship the release", 6),
+            ('x', "Write changelog", 7),
+            ('/', "Tag the build", 8),
+            ('-', "Drop IE support", 9),
+            (' ', "Numbered task", 10),
+            ('?', "Custom status", 18),
+        ]
+    );
+    assert_eq!(
+        tasks.iter().map(Task::is_closed).collect::<Vec<_>>(),
+        [false, true, false, true, false, false]
+    );
+}
+
+#[test]
+fn reads_emoji_and_inline_field_dates() {
+    let tasks = parse_tasks(NOTE);
+    assert_eq!(tasks[0].due.as_deref(), Some("2026-03-01"));
+    assert_eq!(tasks[0].scheduled.as_deref(), Some("2026-02-20"));
+    assert_eq!(tasks[1].completion.as_deref(), Some("2026-02-18"));
+    assert_eq!(tasks[2].due.as_deref(), Some("2026-02-25"));
+    assert_eq!(tasks[4].scheduled.as_deref(), Some("2026-04-01"));
+    assert_eq!(tasks[3].due, None);
+}
+
+#[test]
+fn tracks_nesting_and_sections() {
+    let tasks = parse_tasks(NOTE);
+    assert_eq!(
+        tasks.iter().map(|t| t.parent).collect::<Vec<_>>(),
+        [None, Some(0), Some(0), None, None, None]
+    );
+    assert_eq!(tasks[0].section.as_deref(), Some("Backlog"));
+    assert_eq!(tasks[5].section.as_deref(), Some("Someday"));
+    assert_eq!(tasks[5].indent, 4);
+}
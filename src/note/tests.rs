@@ -73,3 +73,129 @@ fn walk_vault_skips_hidden_dirs() {
     assert_eq!(notes.len(), 1);
     assert_eq!(notes[0].name, "visible");
 }
+
+#[test]
+fn refresh_path_tracks_create_edit_delete() {
+    let (dir, vault) = make_vault();
+    let vault = vault.canonicalize().unwrap();
+    let mut notes = walk_vault(&vault);
+    assert!(notes.is_empty());
+
+    let file = vault.join("Notes/new.md");
+    fs::write(&file, "---\nstatus: draft\n---\n").unwrap();
+    assert!(refresh_path(&mut notes, &vault, &file));
+    assert_eq!(notes.len(), 1);
+    assert!(
+        !refresh_path(&mut notes, &vault, &file),
+        "unchanged content"
+    );
+
+    fs::write(&file, "---\nstatus: done\n---\n").unwrap();
+    assert!(refresh_path(&mut notes, &vault, &file));
+    assert_eq!(
+        notes[0].get_property("status").and_then(Value::as_str),
+        Some("done")
+    );
+
+    fs::remove_file(&file).unwrap();
+    assert!(refresh_path(&mut notes, &vault, &file));
+    assert!(notes.is_empty());
+    drop(dir);
+}
+
+#[test]
+fn refresh_path_handles_folder_moves_and_hidden_dirs() {
+    let (_dir, vault) = make_vault();
+    let vault = vault.canonicalize().unwrap();
+    fs::write(vault.join("Notes/a.md"), "# A").unwrap();
+    fs::write(vault.join("Notes/b.md"), "# B").unwrap();
+    let mut notes = walk_vault(&vault);
+    assert_eq!(notes.len(), 2);
+
+    fs::rename(vault.join("Notes"), vault.join("Archive")).unwrap();
+    assert!(refresh_path(&mut notes, &vault, &vault.join("Notes")));
+    assert!(notes.is_empty());
+    assert!(refresh_path(&mut notes, &vault, &vault.join("Archive")));
+    let mut paths: Vec<&str> = notes.iter().map(|n| n.rel_path.as_str()).collect();
+    paths.sort_unstable();
+    assert_eq!(paths, vec!["Archive/a.md", "Archive/b.md"]);
+
+    fs::write(vault.join(".obsidian/workspace.md"), "# W").unwrap();
+    assert!(!refresh_path(
+        &mut notes,
+        &vault,
+        &vault.join(".obsidian/workspace.md")
+    ));
+}
+
+#[test]
+fn refresh_path_reconciles_a_rescanned_folder() {
+    let (_dir, vault) = make_vault();
+    let vault = vault.canonicalize().unwrap();
+    fs::write(vault.join("Notes/a.md"), "# A").unwrap();
+    fs::write(vault.join("Notes/b.md"), "# B").unwrap();
+    let mut notes = walk_vault(&vault);
+
+    // Changes whose events were missed: one note gone, one added, one edited
+    fs::remove_file(vault.join("Notes/b.md")).unwrap();
+    fs::write(vault.join("Notes/c.md"), "# C").unwrap();
+    fs::write(vault.join("Notes/a.md"), "# A2").unwrap();
+    assert!(refresh_path(&mut notes, &vault, &vault.join("Notes")));
+    let mut paths: Vec<&str> = notes.iter().map(|n| n.rel_path.as_str()).collect();
+    paths.sort_unstable();
+    assert_eq!(paths, vec!["Notes/a.md", "Notes/c.md"]);
+    assert!(notes.iter().any(|n| n.content() == "# A2"));
+
+    assert!(
+        !refresh_path(&mut notes, &vault, &vault),
+        "already up to date"
+    );
+}
+
+#[test]
+fn parses_headings_with_file_line_numbers() {
+    let content = "---\ntitle: x\n---\n# Title\n\n## Plan ##\n```md\n## Not a heading\n```\n#tag is not a heading\n   ### Indented\n";
+    let headings = parse_headings(content);
+    assert_eq!(
+        headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str(), h.line))
+            .collect::<Vec<_>>(),
+        vec![(1, "Title", 4), (2, "Plan", 6), (3, "Indented", 11)]
+    );
+}
+
+#[test]
+fn note_context_exposes_sections() {
+    let (_dir, root) = make_vault();
+    let path = root.join("Notes/Project.md");
+    fs::write(
+        &path,
+        "---\ntags: [type/project]\n---\n# Project\n\n## Work log\n\n- 2026-01-02 Started\n",
+    )
+    .unwrap();
+    let note = NoteContext::from_file(&root, &path).unwrap();
+    assert_eq!(note.headings().len(), 2);
+    let log = note.section("Work log").unwrap();
+    assert_eq!((log.heading.line, log.end), (6, 8));
+    assert_eq!(log.text, "\n- 2026-01-02 Started");
+}
+
+#[test]
+fn from_content_parses_and_set_content_refreshes() {
+    let mut note = NoteContext::from_content(
+        "Projects/Alpha.md",
+        "---\ntags: [type/project]\n---\nSee [[Beta]].\neffort:: 3\n",
+    );
+    assert_eq!(
+        (note.name.as_str(), note.ext.as_str(), note.folder.as_str()),
+        ("Alpha", "md", "Projects")
+    );
+    assert_eq!(note.tags, ["type/project"]);
+    assert_eq!(note.links, ["Beta"]);
+    assert_eq!(note.inline_fields()["effort"], Value::from(3));
+
+    note.set_content("effort:: 5\n");
+    assert!(note.tags.is_empty() && note.links.is_empty());
+    assert_eq!(note.inline_fields()["effort"], Value::from(5));
+}
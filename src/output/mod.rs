@@ -0,0 +1,322 @@
+#[cfg(test)]
+mod tests;
+
+use crate::base::{
+    Combinator, EntryTrace, Explanation, FilterTrace, ResolvedBase, ResolvedView, Row,
+};
+use crate::eval::Trace;
+use crate::vault::{LinkFormat, LinkIndex};
+use serde_json::Value;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Output format for resolved base views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One JSON object per row (default).
+    Jsonl,
+    /// A single JSON array of row objects.
+    Json,
+    Csv,
+    Tsv,
+    /// Markdown table with the file column wikilinked, ready to paste into a note.
+    Markdown,
+    /// Vault-relative file paths, one per line.
+    Paths,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonl" => Ok(Self::Jsonl),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "paths" => Ok(Self::Paths),
+            other => Err(format!(
+                "Unknown format: {other} (expected csv, tsv, markdown, json, jsonl or paths)"
+            )),
+        }
+    }
+}
+
+/// Render every view of a resolved base in the given format.
+///
+/// Markdown links are only checked for name clashes among the rows themselves; use
+/// [`render_linked`] with an index of the whole vault when it is at hand.
+pub fn render(resolved: &ResolvedBase, format: Format) -> String {
+    let links = LinkIndex::from_paths(
+        resolved
+            .views
+            .iter()
+            .flat_map(|v| v.rows.iter().map(|r| r.file.clone())),
+    );
+    render_linked(resolved, format, &links)
+}
+
+/// Render every view of a resolved base in the given format.
+///
+/// Tabular formats use the view's `order` as columns (falling back to `file.name`).
+/// When several views are rendered, CSV/TSV tables are separated by a blank line and
+/// Markdown tables get a `###` heading per view. Markdown links a note by name, or by
+/// path when `links` holds another note with the same name.
+pub fn render_linked(resolved: &ResolvedBase, format: Format, links: &LinkIndex) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Jsonl => {
+            for view in &resolved.views {
+                for row in &view.rows {
+                    let obj = view.row_json(row);
+                    out.push_str(&serde_json::to_string(&obj).unwrap_or_default());
+                    out.push('\n');
+                }
+            }
+        }
+        Format::Json => {
+            let rows: Vec<Value> = resolved
+                .views
+                .iter()
+                .flat_map(|v| v.rows.iter().map(|r| Value::Object(v.row_json(r))))
+                .collect();
+            out.push_str(&serde_json::to_string_pretty(&rows).unwrap_or_default());
+            out.push('\n');
+        }
+        Format::Paths => {
+            for view in &resolved.views {
+                for row in &view.rows {
+                    out.push_str(&row.file);
+                    out.push('\n');
+                }
+            }
+        }
+        Format::Csv | Format::Tsv | Format::Markdown => {
+            let multi = resolved.views.len() > 1;
+            for (i, view) in resolved.views.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                if format == Format::Markdown {
+                    if multi {
+                        let _ = writeln!(out, "### {}\n", view.name);
+                    }
+                    render_markdown(view, links, &mut out);
+                } else {
+                    render_delimited(view, format == Format::Tsv, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Columns for tabular output as `(id, header)` pairs.
+fn table_columns(view: &ResolvedView) -> Vec<(&str, &str)> {
+    if view.columns.is_empty() {
+        return vec![("file.name", "name")];
+    }
+    view.columns
+        .iter()
+        .zip(&view.headers)
+        .map(|(c, h)| (c.as_str(), h.as_str()))
+        .collect()
+}
+
+fn cell<'a>(view: &ResolvedView, row: &'a Row, col: &str) -> Option<&'a Value> {
+    view.columns
+        .iter()
+        .position(|c| c == col)
+        .and_then(|i| row.cells.get(i))
+}
+
+fn render_delimited(view: &ResolvedView, tabs: bool, out: &mut String) {
+    let sep = if tabs { "\t" } else { "," };
+    let field = |s: &str| {
+        if tabs {
+            escape_tsv(s)
+        } else {
+            escape_csv(s)
+        }
+    };
+
+    let columns = table_columns(view);
+    let header: Vec<String> = columns.iter().map(|(_, h)| field(h)).collect();
+    out.push_str(&header.join(sep));
+    out.push('\n');
+
+    for row in &view.rows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|(id, _)| match cell(view, row, id) {
+                Some(v) => field(&plain_text(v)),
+                None if *id == "file.name" => field(&row.name),
+                None => String::new(),
+            })
+            .collect();
+        out.push_str(&fields.join(sep));
+        out.push('\n');
+    }
+}
+
+fn render_markdown(view: &ResolvedView, links: &LinkIndex, out: &mut String) {
+    let columns = table_columns(view);
+
+    let header: Vec<String> = columns.iter().map(|(_, h)| escape_markdown(h)).collect();
+    let _ = writeln!(out, "| {} |", header.join(" | "));
+    let rule: Vec<&str> = columns.iter().map(|_| "---").collect();
+    let _ = writeln!(out, "| {} |", rule.join(" | "));
+
+    for row in &view.rows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|(id, _)| match *id {
+                "file.name" => {
+                    let link = links.link_text(&row.file, "", LinkFormat::Shortest);
+                    format!("[[{}]]", escape_markdown(&link))
+                }
+                "file.path" | "file.fullname" => format!("[[{}]]", escape_markdown(&row.file)),
+                _ => cell(view, row, id)
+                    .map(|v| escape_markdown(&plain_text(v)))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let _ = writeln!(out, "| {} |", fields.join(" | "));
+    }
+}
+
+/// Flatten a JSON cell to text; list items and `key: value` pairs are joined with `, `.
+fn plain_text(val: &Value) -> String {
+    match val {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(plain_text)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| format!("{k}: {}", plain_text(v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn escape_tsv(s: &str) -> String {
+    s.replace("\r\n", " ").replace(['\t', '\n', '\r'], " ")
+}
+
+/// Escape a value for a Markdown table cell: pipes (including wikilink aliases) and newlines.
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+// ─── Explain ────────────────────────────────────────────────
+
+/// Render an explanation as an indented tree: a summary line, then the top-level
+/// filters and each view's filters with every sub-expression's value.
+pub fn render_explanation(exp: &Explanation) -> String {
+    let mut out = String::new();
+
+    let matched: Vec<&str> = exp
+        .views
+        .iter()
+        .filter(|v| v.matched)
+        .map(|v| v.name.as_str())
+        .collect();
+    let summary = if !exp.passes_filters() {
+        "rejected by top-level filters".to_owned()
+    } else if matched.is_empty() {
+        "rejected by view filters".to_owned()
+    } else {
+        format!("matched in {}", matched.join(", "))
+    };
+    let _ = writeln!(out, "{}: {summary}", exp.file);
+
+    match &exp.filters {
+        Some(filter) => {
+            let _ = writeln!(out, "  filters:");
+            write_filter(filter, 2, &mut out);
+        }
+        None => {
+            let _ = writeln!(out, "  filters: (none)");
+        }
+    }
+
+    for view in &exp.views {
+        let _ = write!(out, "  view {}: {}", view.name, mark(view.matched));
+        match &view.filters {
+            Some(filter) => {
+                out.push('\n');
+                write_filter(filter, 2, &mut out);
+            }
+            None => out.push_str(" (no filters)\n"),
+        }
+    }
+    out
+}
+
+fn mark(passed: bool) -> &'static str {
+    if passed {
+        "✓"
+    } else {
+        "✗"
+    }
+}
+
+fn write_filter(filter: &FilterTrace, depth: usize, out: &mut String) {
+    let pad = "  ".repeat(depth);
+    let name = match filter.combinator {
+        Combinator::And => "and",
+        Combinator::Or => "or",
+    };
+    let _ = writeln!(out, "{pad}{name} {}", mark(filter.passed));
+    for entry in &filter.entries {
+        match entry {
+            EntryTrace::Expr {
+                expr,
+                passed,
+                trace: Some(trace),
+            } => {
+                let _ = writeln!(out, "{pad}  {} {expr} → {}", mark(*passed), trace.value);
+                for child in &trace.children {
+                    write_trace(child, depth + 3, out);
+                }
+            }
+            EntryTrace::Expr {
+                expr, trace: None, ..
+            } => {
+                let _ = writeln!(out, "{pad}  ✓ {expr} (unparseable, treated as match)");
+            }
+            EntryTrace::Nested(node) => write_filter(node, depth + 1, out),
+        }
+    }
+}
+
+fn write_trace(trace: &Trace, depth: usize, out: &mut String) {
+    let _ = writeln!(
+        out,
+        "{}{} → {}",
+        "  ".repeat(depth),
+        trace.expr,
+        trace.value
+    );
+    for child in &trace.children {
+        write_trace(child, depth + 1, out);
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+use serde_json::json;
+
+fn sample() -> ResolvedBase {
+    ResolvedBase {
+        views: vec![ResolvedView {
+            name: "Books".into(),
+            view_type: "table".into(),
+            columns: vec!["file.name".into(), "rating".into(), "keywords".into()],
+            headers: vec!["Title".into(), "rating".into(), "keywords".into()],
+            rows: vec![
+                Row {
+                    file: "Library/Books/Dune.md".into(),
+                    name: "Dune".into(),
+                    cells: vec![json!("Dune"), json!(5), json!(["[[Herbert]]", "sci-fi"])],
+                },
+                Row {
+                    file: "Library/Books/Notes, Vol 2.md".into(),
+                    name: "Notes, Vol 2".into(),
+                    cells: vec![
+                        json!("Notes, Vol 2"),
+                        serde_json::Value::Null,
+                        json!(["[[Ann|A. N.]]"]),
+                    ],
+                },
+            ],
+        }],
+    }
+}
+
+#[test]
+fn parses_format_names() {
+    assert_eq!("CSV".parse::<Format>().unwrap(), Format::Csv);
+    assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+    assert!("xml".parse::<Format>().is_err());
+}
+
+#[test]
+fn csv_quotes_and_joins_lists() {
+    let out = render(&sample(), Format::Csv);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "Title,rating,keywords");
+    assert_eq!(lines[1], "Dune,5,\"[[Herbert]], sci-fi\"");
+    assert_eq!(lines[2], "\"Notes, Vol 2\",,[[Ann|A. N.]]");
+}
+
+#[test]
+fn csv_joins_numeric_lists_and_mappings() {
+    let mut base = sample();
+    base.views[0].rows[0].cells[2] = json!([4, 5]);
+    base.views[0].rows[1].cells[2] = json!({"pages": 412, "series": "Dune"});
+    let out = render(&base, Format::Csv);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[1], "Dune,5,\"4, 5\"");
+    assert_eq!(lines[2], "\"Notes, Vol 2\",,\"pages: 412, series: Dune\"");
+}
+
+#[test]
+fn tsv_uses_tabs() {
+    let out = render(&sample(), Format::Tsv);
+    assert_eq!(out.lines().nth(1), Some("Dune\t5\t[[Herbert]], sci-fi"));
+}
+
+#[test]
+fn markdown_wikilinks_file_and_escapes_pipes() {
+    let out = render(&sample(), Format::Markdown);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "| Title | rating | keywords |");
+    assert_eq!(lines[1], "| --- | --- | --- |");
+    assert_eq!(lines[2], "| [[Dune]] | 5 | [[Herbert]], sci-fi |");
+    assert_eq!(lines[3], "| [[Notes, Vol 2]] |  | [[Ann\\|A. N.]] |");
+}
+
+#[test]
+fn markdown_links_clashing_names_by_path() {
+    let mut base = sample();
+    base.views[0].rows[1].file = "Archive/Dune.md".into();
+    base.views[0].rows[1].name = "Dune".into();
+    let out = render(&base, Format::Markdown);
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines[2].starts_with("| [[Library/Books/Dune]] |"));
+    assert!(lines[3].starts_with("| [[Archive/Dune]] |"));
+
+    // A clash with a note outside the result counts too
+    let vault = LinkIndex::from_paths(["Library/Books/Dune.md".into(), "Old/Dune.md".into()]);
+    let out = render_linked(&sample(), Format::Markdown, &vault);
+    assert!(out
+        .lines()
+        .nth(2)
+        .unwrap()
+        .starts_with("| [[Library/Books/Dune]] |"));
+}
+
+#[test]
+fn markdown_escapes_newlines() {
+    assert_eq!(escape_markdown("a\nb|c"), "a<br>b\\|c");
+}
+
+#[test]
+fn json_is_single_array() {
+    let out = render(&sample(), Format::Json);
+    let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(parsed.as_array().unwrap().len(), 2);
+    assert_eq!(parsed[0]["view"], "Books");
+}
+
+#[test]
+fn empty_order_falls_back_to_name() {
+    let mut resolved = sample();
+    resolved.views[0].columns.clear();
+    resolved.views[0].headers.clear();
+    for row in &mut resolved.views[0].rows {
+        row.cells.clear();
+    }
+    let out = render(&resolved, Format::Csv);
+    assert_eq!(out, "name\nDune\n\"Notes, Vol 2\"\n");
+}
+
+#[test]
+fn explanation_tree() {
+    use crate::base::{Combinator, EntryTrace, FilterTrace, ViewExplanation};
+    use crate::eval::{Trace, Val};
+
+    let exp = Explanation {
+        file: "Notes/Idea.md".into(),
+        filters: None,
+        views: vec![ViewExplanation {
+            name: "Books".into(),
+            filters: Some(FilterTrace {
+                combinator: Combinator::And,
+                passed: false,
+                entries: vec![EntryTrace::Expr {
+                    expr: "rating >= 4".into(),
+                    passed: false,
+                    trace: Some(Trace {
+                        expr: "rating >= 4".into(),
+                        value: Val::Bool(false),
+                        children: vec![Trace {
+                            expr: "rating".into(),
+                            value: Val::Null,
+                            children: Vec::new(),
+                        }],
+                    }),
+                }],
+            }),
+            matched: false,
+        }],
+    };
+
+    assert_eq!(
+        render_explanation(&exp),
+        "Notes/Idea.md: rejected by view filters\n  filters: (none)\n  view Books: ✗\n    and ✗\n      ✗ rating >= 4 → false\n          rating → null\n"
+    );
+}
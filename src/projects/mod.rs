@@ -0,0 +1,276 @@
+#[cfg(test)]
+mod tests;
+
+use crate::note::{self, NoteContext, Section, Structure};
+use regex::Regex;
+use serde_json::json;
+use serde_yaml::Value;
+use std::fmt;
+use std::sync::LazyLock;
+
+pub const STATUSES: &[&str] = &["planned", "active", "on-hold", "completed"];
+pub const PRIORITIES: &[&str] = &["Critical", "High", "Medium", "Low"];
+
+static WORK_LOG_DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[\[\d{4}-\d{2}-\d{2}\]\]$").unwrap());
+
+/// Which project-management rule a project breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Not a folder note at `Projects/<Name>/<Name>.md`.
+    Layout,
+    /// `project.status` missing, not a scalar, or outside `STATUSES`.
+    Status,
+    /// `project.priority` missing, not a scalar, or outside `PRIORITIES`.
+    Priority,
+    /// A completed project with an open task.
+    OpenTask,
+    /// A completed project without an `updated:` date.
+    Updated,
+    /// A work-log entry not headed `### [[YYYY-MM-DD]]`.
+    WorkLog,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rule::Layout => "layout",
+            Rule::Status => "status",
+            Rule::Priority => "priority",
+            Rule::OpenTask => "open-task",
+            Rule::Updated => "updated",
+            Rule::WorkLog => "work-log",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Vault-relative path of the project note.
+    pub file: String,
+    /// 1-based line in the note.
+    pub line: usize,
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "file": self.file,
+            "line": self.line,
+            "rule": self.rule.to_string(),
+            "message": self.message,
+        })
+    }
+}
+
+/// A note is a project when it is tagged `type/project` or has any `project.*` property.
+pub fn is_project(note: &NoteContext) -> bool {
+    note.has_tag("type/project") || note.properties.keys().any(|k| k.starts_with("project."))
+}
+
+/// Check every project in the vault, in path order.
+pub fn check(notes: &[NoteContext]) -> Vec<Violation> {
+    let mut projects: Vec<&NoteContext> = notes.iter().filter(|n| is_project(n)).collect();
+    projects.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    projects.into_iter().flat_map(check_project).collect()
+}
+
+/// Check one project note against the project-management conventions.
+pub fn check_project(note: &NoteContext) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut report = |line: usize, rule: Rule, message: String| {
+        violations.push(Violation {
+            file: note.rel_path.clone(),
+            line,
+            rule,
+            message,
+        });
+    };
+
+    let expected = format!("Projects/{0}/{0}.{1}", note.name, note.ext);
+    if note.rel_path != expected {
+        report(
+            1,
+            Rule::Layout,
+            format!("project note should be at {expected}"),
+        );
+    }
+
+    let status = enum_property(note, "project.status", STATUSES);
+    if let Err(message) = &status {
+        report(
+            note::frontmatter_line(note.content(), "project.status"),
+            Rule::Status,
+            message.clone(),
+        );
+    }
+    if let Err(message) = enum_property(note, "project.priority", PRIORITIES) {
+        report(
+            note::frontmatter_line(note.content(), "project.priority"),
+            Rule::Priority,
+            message,
+        );
+    }
+
+    if status.as_deref() == Ok("completed") {
+        let updated = note.get_property("updated");
+        if updated.is_none_or(|v| v.is_null() || v.as_str() == Some("")) {
+            report(
+                note::frontmatter_line(note.content(), "updated"),
+                Rule::Updated,
+                "completed project needs an `updated:` date".to_owned(),
+            );
+        }
+        for task in note.tasks().iter().filter(|t| !t.is_closed()) {
+            report(
+                task.line,
+                Rule::OpenTask,
+                format!(
+                    "completed project has an open task: {}; promote it to Orchestration/Backlog.md or close it",
+                    task.text
+                ),
+            );
+        }
+    }
+
+    if let Some(log) = note.section("Work log") {
+        for heading in note.headings() {
+            let in_log = heading.line > log.heading.line && heading.line <= log.end;
+            if in_log
+                && heading.level == log.heading.level + 1
+                && !WORK_LOG_DATE_RE.is_match(&heading.text)
+            {
+                report(
+                    heading.line,
+                    Rule::WorkLog,
+                    format!(
+                        "work-log entry `{}` should be headed `[[YYYY-MM-DD]]`",
+                        heading.text
+                    ),
+                );
+            }
+        }
+    }
+
+    violations
+}
+
+/// The property as one of `allowed`, or why it is not.
+fn enum_property(note: &NoteContext, key: &str, allowed: &[&str]) -> Result<String, String> {
+    match note.get_property(key) {
+        None | Some(Value::Null) => Err(format!("missing `{key}`")),
+        Some(Value::String(s)) if allowed.contains(&s.as_str()) => Ok(s.clone()),
+        Some(Value::String(s)) => Err(format!(
+            "`{key}` is `{s}`; expected one of {}",
+            allowed.join(", ")
+        )),
+        Some(_) => Err(format!("`{key}` must be a single value")),
+    }
+}
+
+/// One JSON object per line.
+pub fn render_jsonl(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|v| v.to_json().to_string() + "\n")
+        .collect()
+}
+
+// ─── Work log ───────────────────────────────────────────────
+
+/// Add `- entry` under `### [[date]]` in the note's `## Work log`.
+///
+/// The date heading is created if missing, in the order the log already uses (oldest or
+/// newest first), and the section itself is appended at the end of the note if absent.
+/// Only lines inside the work log change; frontmatter and other sections are untouched.
+pub fn append_work_log(content: &str, date: &str, entry: &str) -> String {
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    // Lines keep their own endings; only inserted lines take the note's `eol`
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_owned).collect();
+    let bullet = format!("- {entry}");
+    let dated = format!("[[{date}]]");
+
+    let structure = Structure::parse(content);
+    let Some(log) = structure.section(content, "Work log") else {
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        if let Some(last) = lines.last_mut() {
+            terminate(last, eol);
+            lines.push(eol.to_owned());
+        }
+        let section = ["## Work log", "", &format!("### {dated}"), &bullet];
+        lines.extend(section.iter().map(|l| format!("{l}{eol}")));
+        return lines.concat();
+    };
+
+    let entries: Vec<Section> = structure
+        .sections(content)
+        .into_iter()
+        .filter(|s| {
+            s.heading.level == log.heading.level + 1
+                && s.heading.line > log.heading.line
+                && s.heading.line <= log.end
+        })
+        .collect();
+
+    // 1-based line after which new lines go, and what to insert there
+    let (after, insert) = if let Some(day) = entries.iter().find(|s| s.heading.text == dated) {
+        (
+            last_content_line(&lines, day.heading.line, day.end),
+            vec![bullet],
+        )
+    } else {
+        let heading = format!("{} {dated}", "#".repeat(log.heading.level + 1));
+        let logged: Vec<&str> = entries
+            .iter()
+            .filter(|s| WORK_LOG_DATE_RE.is_match(&s.heading.text))
+            .map(|s| s.heading.text.as_str())
+            .collect();
+        let newest_first = logged.len() > 1 && logged[0] > logged[logged.len() - 1];
+        let next = entries.iter().find(|s| {
+            WORK_LOG_DATE_RE.is_match(&s.heading.text)
+                && if newest_first {
+                    s.heading.text.as_str() < dated.as_str()
+                } else {
+                    s.heading.text.as_str() > dated.as_str()
+                }
+        });
+        match next {
+            Some(next) => (next.heading.line - 1, vec![heading, bullet, String::new()]),
+            None => (
+                last_content_line(&lines, log.heading.line, log.end),
+                vec![String::new(), heading, bullet],
+            ),
+        }
+    };
+
+    if after == lines.len() {
+        if let Some(last) = lines.last_mut() {
+            terminate(last, eol);
+        }
+    }
+    lines.splice(after..after, insert.into_iter().map(|l| l + eol));
+    lines.concat()
+}
+
+/// End `line` with `eol` unless it already ends with a line break.
+fn terminate(line: &mut String, eol: &str) {
+    if !line.ends_with('\n') {
+        line.push_str(eol);
+    }
+}
+
+/// Last non-blank line in `first..=last` (1-based), never before `first`.
+fn last_content_line(lines: &[String], first: usize, last: usize) -> usize {
+    (first..=last)
+        .rev()
+        .find(|&n| lines.get(n - 1).is_some_and(|l| !l.trim().is_empty()))
+        .unwrap_or(first)
+}
@@ -0,0 +1,168 @@
+use super::*;
+use std::collections::HashMap;
+
+fn project(rel_path: &str, content: &str) -> NoteContext {
+    NoteContext::from_content(rel_path, content)
+}
+
+fn rules(violations: &[Violation]) -> Vec<(usize, Rule)> {
+    violations.iter().map(|v| (v.line, v.rule)).collect()
+}
+
+const GOOD: &str = "---
+project.status: completed
+project.priority: High
+updated: 2026-02-01
+---
+# Alpha
+
+- [x] Ship it
+- [-] Dropped idea
+
+## Work log
+
+### [[2026-01-15]]
+- Kickoff
+";
+
+#[test]
+fn conforming_project_passes() {
+    assert_eq!(check_project(&project("Projects/Alpha/Alpha.md", GOOD)), []);
+}
+
+#[test]
+fn reports_layout_and_enum_violations() {
+    let content = "---\nproject.status: [active]\nproject.priority: urgent\n---\n# Beta\n";
+    let violations = check_project(&project("Projects/Beta.md", content));
+    assert_eq!(
+        rules(&violations),
+        [(1, Rule::Layout), (2, Rule::Status), (3, Rule::Priority)]
+    );
+    assert_eq!(
+        violations[0].message,
+        "project note should be at Projects/Beta/Beta.md"
+    );
+    assert_eq!(
+        violations[2].message,
+        "`project.priority` is `urgent`; expected one of Critical, High, Medium, Low"
+    );
+}
+
+#[test]
+fn completed_projects_need_closed_tasks_and_updated() {
+    let content = GOOD
+        .replace("updated: 2026-02-01\n", "")
+        .replace("- [-] Dropped idea", "- [ ] Still open");
+    let violations = check_project(&project("Projects/Alpha/Alpha.md", &content));
+    assert_eq!(
+        rules(&violations),
+        [(1, Rule::Updated), (8, Rule::OpenTask)]
+    );
+}
+
+#[test]
+fn work_log_entries_are_date_headed() {
+    let content = GOOD.replace("### [[2026-01-15]]", "### January");
+    let violations = check_project(&project("Projects/Alpha/Alpha.md", &content));
+    assert_eq!(rules(&violations), [(13, Rule::WorkLog)]);
+}
+
+#[test]
+fn check_only_looks_at_projects() {
+    let mut plain = project("Notes/Idea.md", "# Idea\n");
+    plain.properties = HashMap::new();
+    let mut tagged = project("Projects/Gamma/Gamma.md", "# Gamma\n");
+    tagged.tags = vec!["type/project".to_owned()];
+    let violations = check(&[plain, tagged]);
+    assert_eq!(rules(&violations), [(1, Rule::Status), (1, Rule::Priority)]);
+}
+
+const LOGGED: &str = "---
+project.status: active
+---
+# Alpha
+
+## Work log
+
+### [[2026-01-10]]
+- Kickoff
+
+### [[2026-01-20]]
+- Review
+
+## Notes
+Keep me
+";
+
+#[test]
+fn work_log_appends_to_existing_day() {
+    let out = append_work_log(LOGGED, "2026-01-10", "Scoped [[Beta]]");
+    assert_eq!(
+        out,
+        LOGGED.replace("- Kickoff\n", "- Kickoff\n- Scoped [[Beta]]\n")
+    );
+}
+
+#[test]
+fn work_log_inserts_days_in_order() {
+    let out = append_work_log(LOGGED, "2026-01-15", "Middle");
+    assert_eq!(
+        out,
+        LOGGED.replace(
+            "### [[2026-01-20]]",
+            "### [[2026-01-15]]\n- Middle\n\n### [[2026-01-20]]"
+        )
+    );
+
+    let out = append_work_log(LOGGED, "2026-02-01", "Latest");
+    assert_eq!(
+        out,
+        LOGGED.replace("- Review\n", "- Review\n\n### [[2026-02-01]]\n- Latest\n")
+    );
+
+    let newest_first = "## Work log\n\n### [[2026-01-20]]\n- B\n\n### [[2026-01-10]]\n- A\n";
+    assert_eq!(
+        append_work_log(newest_first, "2026-01-15", "Middle"),
+        newest_first.replace(
+            "### [[2026-01-10]]",
+            "### [[2026-01-15]]\n- Middle\n\n### [[2026-01-10]]"
+        )
+    );
+}
+
+#[test]
+fn work_log_section_is_created_at_the_end() {
+    let content = "---\ntitle: x\n---\n# Alpha\n\nBody\n\n";
+    assert_eq!(
+        append_work_log(content, "2026-01-10", "Started"),
+        "---\ntitle: x\n---\n# Alpha\n\nBody\n\n## Work log\n\n### [[2026-01-10]]\n- Started\n"
+    );
+}
+
+#[test]
+fn work_log_keeps_crlf_line_endings() {
+    let crlf = LOGGED.replace('\n', "\r\n");
+    let out = append_work_log(&crlf, "2026-01-15", "Middle");
+    assert_eq!(
+        out,
+        crlf.replace(
+            "### [[2026-01-20]]",
+            "### [[2026-01-15]]\r\n- Middle\r\n\r\n### [[2026-01-20]]"
+        )
+    );
+
+    let out = append_work_log(&crlf, "2026-02-01", "Latest");
+    assert_eq!(
+        out,
+        crlf.replace(
+            "- Review\r\n",
+            "- Review\r\n\r\n### [[2026-02-01]]\r\n- Latest\r\n"
+        )
+    );
+
+    let unterminated = "# Alpha\r\n\r\nBody";
+    assert_eq!(
+        append_work_log(unterminated, "2026-01-10", "Started"),
+        "# Alpha\r\n\r\nBody\r\n\r\n## Work log\r\n\r\n### [[2026-01-10]]\r\n- Started\r\n"
+    );
+}
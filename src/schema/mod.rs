@@ -0,0 +1,428 @@
+#[cfg(test)]
+mod tests;
+
+use crate::note::{self, Heading};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use serde_yaml::Value;
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A `.mdschema` file: frontmatter field rules and the expected heading structure.
+///
+/// ```yaml
+/// frontmatter:
+///   fields:
+///     - name: tlp
+///       type: string
+///       enum: [RED, AMBER, GREEN, CLEAR]
+///     - name: tags
+///       type: array
+///       optional: true
+/// structure:
+///   - heading: "# {{.filename}}"
+///     children:
+///       - heading: "## Plan"
+///       - heading: { pattern: "## Log.*", regex: true }
+///         optional: true
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Schema {
+    #[serde(default)]
+    pub frontmatter: FrontmatterRules,
+    #[serde(default)]
+    pub structure: Vec<SectionRule>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FrontmatterRules {
+    /// Whether a note may have no frontmatter at all.
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub fields: Vec<FieldRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldRule {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: Option<FieldType>,
+    /// Fields are required unless marked optional.
+    #[serde(default)]
+    pub optional: bool,
+    /// Allowed values; for arrays, every item must be one of them.
+    #[serde(rename = "enum")]
+    pub allowed: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Boolean,
+    #[serde(alias = "list")]
+    Array,
+    Object,
+    /// `YYYY-MM-DD`
+    Date,
+    /// A `[[wikilink]]` string.
+    Link,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SectionRule {
+    pub heading: HeadingPattern,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub children: Vec<SectionRule>,
+}
+
+/// Heading text to match, written with its `#` marks; `{{.filename}}` is the note's name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum HeadingPattern {
+    Literal(String),
+    Pattern {
+        pattern: String,
+        #[serde(default)]
+        regex: bool,
+    },
+}
+
+/// What a violation is about, printed as `[frontmatter]` or `[structure]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Frontmatter,
+    Structure,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Category::Frontmatter => "frontmatter",
+            Category::Structure => "structure",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 1-based line in the note.
+    pub line: usize,
+    pub category: Category,
+    pub message: String,
+}
+
+/// Violations found in one file.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub path: String,
+    pub violations: Vec<Violation>,
+}
+
+impl Schema {
+    pub fn parse_file(path: &Path) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        Self::parse_str(&content).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn parse_str(content: &str) -> Result<Self, String> {
+        let schema: Self =
+            serde_yaml::from_str(content).map_err(|e| format!("invalid schema: {e}"))?;
+        check_patterns(&schema.structure)?;
+        Ok(schema)
+    }
+
+    /// Check a note's content; `name` is the file name without extension.
+    pub fn validate(&self, content: &str, name: &str) -> Vec<Violation> {
+        let mut violations = self.validate_frontmatter(content);
+        let headings = note::parse_headings(content);
+        check_sections(&self.structure, &headings, 0, name, &mut violations);
+        violations.sort_by_key(|v| v.line);
+        violations
+    }
+
+    fn validate_frontmatter(&self, content: &str) -> Vec<Violation> {
+        let violation = |line, message: String| Violation {
+            line,
+            category: Category::Frontmatter,
+            message,
+        };
+        if !content.starts_with("---") {
+            if self.frontmatter.optional || self.frontmatter.fields.iter().all(|f| f.optional) {
+                return Vec::new();
+            }
+            return vec![violation(1, "missing frontmatter".to_owned())];
+        }
+
+        let (properties, _) = note::parse_frontmatter(content);
+        let mut violations = Vec::new();
+        for field in &self.frontmatter.fields {
+            let Some(value) = properties.get(&field.name) else {
+                if !field.optional {
+                    violations.push(violation(
+                        1,
+                        format!("missing required field '{}'", field.name),
+                    ));
+                }
+                continue;
+            };
+            let line = note::frontmatter_line(content, &field.name);
+            if let Some(kind) = field.kind.filter(|&k| !has_type(value, k)) {
+                violations.push(violation(
+                    line,
+                    format!("field '{}' should be {}", field.name, type_name(kind)),
+                ));
+            }
+            if let Some(allowed) = &field.allowed {
+                let values = match value {
+                    Value::Sequence(items) => items.clone(),
+                    other => vec![other.clone()],
+                };
+                for value in values.iter().filter(|v| !allowed.contains(v)) {
+                    violations.push(violation(
+                        line,
+                        format!(
+                            "field '{}' has value {} (allowed: {})",
+                            field.name,
+                            yaml_inline(value),
+                            allowed
+                                .iter()
+                                .map(yaml_inline)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// Match sibling rules in order against the headings of one section.
+///
+/// `headings` are the section's descendants; a rule that matches only before the previous
+/// match is out of order, one that matches nowhere is missing unless optional.
+fn check_sections(
+    rules: &[SectionRule],
+    headings: &[Heading],
+    parent_line: usize,
+    name: &str,
+    violations: &mut Vec<Violation>,
+) {
+    let mut cursor = 0;
+    for rule in rules {
+        let (level, matcher) = match rule.heading.compile(name) {
+            Ok(compiled) => compiled,
+            Err(message) => {
+                violations.push(Violation {
+                    line: parent_line.max(1),
+                    category: Category::Structure,
+                    message,
+                });
+                continue;
+            }
+        };
+        let is_match = |h: &Heading| h.level == level && matcher.is_match(&h.text);
+        if let Some(offset) = headings[cursor..].iter().position(is_match) {
+            let index = cursor + offset;
+            let end = headings[index + 1..]
+                .iter()
+                .position(|h| h.level <= level)
+                .map_or(headings.len(), |i| index + 1 + i);
+            check_sections(
+                &rule.children,
+                &headings[index + 1..end],
+                headings[index].line,
+                name,
+                violations,
+            );
+            cursor = index + 1;
+        } else if let Some(found) = headings[..cursor].iter().find(|h| is_match(h)) {
+            violations.push(Violation {
+                line: found.line,
+                category: Category::Structure,
+                message: format!("heading \"{}\" is out of order", rule.heading.display(name)),
+            });
+        } else if !rule.optional {
+            violations.push(Violation {
+                line: parent_line.max(1),
+                category: Category::Structure,
+                message: format!(
+                    "missing required section \"{}\"",
+                    rule.heading.display(name)
+                ),
+            });
+        }
+    }
+}
+
+/// Reject regex headings that do not compile, with a stand-in for `{{.filename}}`.
+fn check_patterns(rules: &[SectionRule]) -> Result<(), String> {
+    for rule in rules {
+        rule.heading.compile("x")?;
+        check_patterns(&rule.children)?;
+    }
+    Ok(())
+}
+
+impl HeadingPattern {
+    /// Heading level and a matcher for the heading text.
+    fn compile(&self, name: &str) -> Result<(usize, Regex), String> {
+        let (raw, is_regex) = match self {
+            HeadingPattern::Literal(text) => (text.as_str(), false),
+            HeadingPattern::Pattern { pattern, regex } => (pattern.as_str(), *regex),
+        };
+        let level = raw.chars().take_while(|&c| c == '#').count();
+        let text = raw[level..].trim();
+        let pattern = if is_regex {
+            text.replace("{{.filename}}", &regex::escape(name))
+        } else {
+            regex::escape(&text.replace("{{.filename}}", name))
+        };
+        let matcher = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|e| format!("invalid heading pattern {raw:?}: {e}"))?;
+        Ok((level, matcher))
+    }
+
+    fn display(&self, name: &str) -> String {
+        match self {
+            HeadingPattern::Literal(text) | HeadingPattern::Pattern { pattern: text, .. } => {
+                text.replace("{{.filename}}", name)
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, kind: FieldType) -> bool {
+    match kind {
+        FieldType::String => value.is_string(),
+        FieldType::Number => value.is_number(),
+        FieldType::Boolean => value.is_bool(),
+        FieldType::Array => value.is_sequence(),
+        FieldType::Object => value.is_mapping(),
+        FieldType::Date => value
+            .as_str()
+            .is_some_and(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()),
+        FieldType::Link => value
+            .as_str()
+            .is_some_and(|s| s.starts_with("[[") && s.ends_with("]]")),
+    }
+}
+
+fn type_name(kind: FieldType) -> &'static str {
+    match kind {
+        FieldType::String => "a string",
+        FieldType::Number => "a number",
+        FieldType::Boolean => "a boolean",
+        FieldType::Array => "an array",
+        FieldType::Object => "an object",
+        FieldType::Date => "a date (YYYY-MM-DD)",
+        FieldType::Link => "a [[wikilink]]",
+    }
+}
+
+fn yaml_inline(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+// ─── Checking files ─────────────────────────────────────────
+
+/// Expand files, directories (walked for `.md`) and glob patterns such as `Daily/**/*.md`.
+pub fn expand_paths(args: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut paths = Vec::new();
+    for arg in args {
+        if arg.contains(['*', '?', '[']) {
+            let entries = glob::glob(arg).map_err(|e| format!("invalid glob {arg}: {e}"))?;
+            paths.extend(entries.filter_map(Result::ok).filter(|p| p.is_file()));
+        } else if Path::new(arg).is_dir() {
+            paths.extend(
+                walkdir::WalkDir::new(arg)
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .map(walkdir::DirEntry::into_path)
+                    .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "md")),
+            );
+        } else {
+            paths.push(PathBuf::from(arg));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Validate every file; unreadable files are reported as a violation on line 1.
+pub fn check(schema: &Schema, paths: &[PathBuf]) -> Vec<FileReport> {
+    paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let violations = match fs::read_to_string(path) {
+                Ok(content) => schema.validate(&content, &name),
+                Err(e) => vec![Violation {
+                    line: 1,
+                    category: Category::Structure,
+                    message: format!("cannot read file: {e}"),
+                }],
+            };
+            FileReport {
+                path: path.to_string_lossy().into_owned(),
+                violations,
+            }
+        })
+        .collect()
+}
+
+/// mdschema-style report: `✓`/`✗` per file, violations with line and category, then a summary.
+pub fn render_human(reports: &[FileReport]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        if report.violations.is_empty() {
+            let _ = writeln!(out, "✓ {}", report.path);
+            continue;
+        }
+        let _ = writeln!(out, "✗ {}", report.path);
+        for v in &report.violations {
+            let _ = writeln!(out, "  {}: [{}] {}", v.line, v.category, v.message);
+        }
+    }
+    let failing = reports.iter().filter(|r| !r.violations.is_empty()).count();
+    let _ = writeln!(
+        out,
+        "\n{} file(s), {} passing, {failing} failing",
+        reports.len(),
+        reports.len() - failing
+    );
+    out
+}
+
+/// One JSON object per file in an array.
+pub fn render_json(reports: &[FileReport]) -> String {
+    let files: Vec<serde_json::Value> = reports
+        .iter()
+        .map(|r| {
+            json!({
+                "file": r.path,
+                "valid": r.violations.is_empty(),
+                "violations": r.violations.iter().map(|v| json!({
+                    "line": v.line,
+                    "category": v.category.to_string(),
+                    "message": v.message,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&files).unwrap_or_default() + "\n"
+}
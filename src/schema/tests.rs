@@ -0,0 +1,137 @@
+use super::*;
+
+const SCHEMA: &str = r###"
+frontmatter:
+  fields:
+    - name: tlp
+      type: string
+      enum: [RED, AMBER, GREEN, CLEAR]
+    - name: date
+      type: date
+    - name: tags
+      type: array
+      optional: true
+      enum: [type/journal, type/journal/daily]
+structure:
+  - heading: "# {{.filename}}"
+    children:
+      - heading: "## Plan"
+      - heading: { pattern: "## Log( \\(.*\\))?", regex: true }
+        optional: true
+      - heading: "## Review"
+"###;
+
+fn validate(content: &str) -> Vec<(usize, Category, String)> {
+    Schema::parse_str(SCHEMA)
+        .unwrap()
+        .validate(content, "2026-01-15")
+        .into_iter()
+        .map(|v| (v.line, v.category, v.message))
+        .collect()
+}
+
+#[test]
+fn valid_note_has_no_violations() {
+    let content = "---\ntlp: AMBER\ndate: 2026-01-15\ntags:\n  - type/journal\n---\n# 2026-01-15\n\n## Plan\n\n## Log (morning)\n\n### Extra\n\n## Review\n";
+    assert_eq!(validate(content), vec![]);
+}
+
+#[test]
+fn reports_frontmatter_fields_with_lines() {
+    let content = "---\ntlp: PURPLE\ndate: 15.01.2026\ntags: [type/note]\n---\n# 2026-01-15\n## Plan\n## Review\n";
+    assert_eq!(
+        validate(content),
+        vec![
+            (
+                2,
+                Category::Frontmatter,
+                r#"field 'tlp' has value "PURPLE" (allowed: "RED", "AMBER", "GREEN", "CLEAR")"#
+                    .to_owned()
+            ),
+            (
+                3,
+                Category::Frontmatter,
+                "field 'date' should be a date (YYYY-MM-DD)".to_owned()
+            ),
+            (
+                4,
+                Category::Frontmatter,
+                r#"field 'tags' has value "type/note" (allowed: "type/journal", "type/journal/daily")"#
+                    .to_owned()
+            ),
+        ]
+    );
+    assert_eq!(
+        validate("# 2026-01-15\n## Plan\n## Review\n"),
+        vec![(1, Category::Frontmatter, "missing frontmatter".to_owned())]
+    );
+}
+
+#[test]
+fn reports_missing_and_out_of_order_sections() {
+    let content = "---\ntlp: RED\ndate: 2026-01-15\n---\n# 2026-01-15\n\n## Review\n\n## Plan\n";
+    assert_eq!(
+        validate(content),
+        vec![(
+            7,
+            Category::Structure,
+            "heading \"## Review\" is out of order".to_owned()
+        )]
+    );
+    let content = "---\ntlp: RED\ndate: 2026-01-15\n---\n# Other title\n## Plan\n";
+    assert_eq!(
+        validate(content),
+        vec![(
+            1,
+            Category::Structure,
+            "missing required section \"# 2026-01-15\"".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn rejects_invalid_schemas() {
+    assert!(Schema::parse_str("structure: 3").is_err());
+    let err = Schema::parse_str("structure:\n  - heading: { pattern: \"## (\", regex: true }\n")
+        .unwrap_err();
+    assert!(err.starts_with("invalid heading pattern"));
+}
+
+#[test]
+fn filename_patterns_validate_and_match_literally() {
+    let schema = Schema::parse_str(
+        "structure:\n  - heading: { pattern: \"# {{.filename}}.*\", regex: true }\n",
+    )
+    .unwrap();
+    assert!(schema.validate("# C++ (draft)\n", "C++ (draft)").is_empty());
+    assert!(schema
+        .validate("# C++ (draft) v2\n", "C++ (draft)")
+        .is_empty());
+    assert_eq!(schema.validate("# Cpp\n", "C++ (draft)").len(), 1);
+}
+
+#[test]
+fn renders_human_and_json_reports() {
+    let reports = vec![
+        FileReport {
+            path: "a.md".to_owned(),
+            violations: vec![],
+        },
+        FileReport {
+            path: "b.md".to_owned(),
+            violations: vec![Violation {
+                line: 3,
+                category: Category::Structure,
+                message: "missing required section \"## Plan\"".to_owned(),
+            }],
+        },
+    ];
+    assert_eq!(
+        render_human(&reports),
+        "✓ a.md\n✗ b.md\n  3: [structure] missing required section \"## Plan\"\n\n2 file(s), 1 passing, 1 failing\n"
+    );
+    let json: serde_json::Value = serde_json::from_str(&render_json(&reports)).unwrap();
+    assert_eq!(json[1]["valid"], false);
+    assert_eq!(json[1]["violations"][0]["category"], "structure");
+    assert_eq!(json[1]["violations"][0]["line"], 3);
+}
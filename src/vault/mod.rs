@@ -1,7 +1,17 @@
 #[cfg(test)]
 mod tests;
 
+use crate::eval::{EvalContext, Expression, ThisContext};
+use crate::note::{self, Frontmatter, NoteContext, Structure};
+use regex::Regex;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use walkdir::WalkDir;
 
 /// Walk up from `start` looking for an `.obsidian/` directory.
 fn find_vault_from_dir(start: &Path) -> Option<PathBuf> {
@@ -26,3 +36,652 @@ pub fn find_vault_from_cwd() -> Option<PathBuf> {
     let cwd = std::env::current_dir().ok()?;
     find_vault_from_dir(&cwd)
 }
+
+// ─── Edits ──────────────────────────────────────────────────
+
+/// A pending rewrite of one note, produced by a vault-wide operation.
+#[derive(Debug, Clone)]
+pub struct FileEdit {
+    pub path: PathBuf,
+    pub rel_path: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl FileEdit {
+    /// Unified diff of the rewrite with `a/` and `b/` vault-relative headers.
+    pub fn diff(&self) -> String {
+        similar::TextDiff::from_lines(&self.before, &self.after)
+            .unified_diff()
+            .header(
+                &format!("a/{}", self.rel_path),
+                &format!("b/{}", self.rel_path),
+            )
+            .to_string()
+    }
+}
+
+/// Write every edit atomically. All new contents are staged in temp files first, so a
+/// failure while writing (a full disk, a read-only folder) leaves every note untouched.
+pub fn apply(edits: &[FileEdit]) -> Result<(), String> {
+    stage(edits, None)?.commit()
+}
+
+/// Stage every edit, removing the temp files already written when one fails.
+///
+/// `moved` is a `(destination, source)` pair for a note that is not in place yet: its edit
+/// takes the permissions of the source file.
+fn stage(edits: &[FileEdit], moved: Option<(&Path, &Path)>) -> Result<Staged, String> {
+    let mut staged = Staged::default();
+    for edit in edits {
+        let source = moved
+            .filter(|(dest, _)| *dest == edit.path)
+            .map(|(_, source)| source);
+        let tmp = write_temp(&edit.path, &edit.after, source)
+            .map_err(|e| format!("{}: {e}", edit.rel_path))?;
+        staged
+            .files
+            .push_back((tmp, edit.path.clone(), edit.rel_path.clone()));
+    }
+    Ok(staged)
+}
+
+/// Temp files waiting to be renamed over their notes; whatever is left is removed on drop.
+#[derive(Default)]
+struct Staged {
+    /// Temp file, destination and vault-relative path for errors.
+    files: VecDeque<(PathBuf, PathBuf, String)>,
+}
+
+impl Staged {
+    fn commit(mut self) -> Result<(), String> {
+        while let Some((tmp, path, rel_path)) = self.files.pop_front() {
+            if let Err(e) = fs::rename(&tmp, &path) {
+                let _ = fs::remove_file(&tmp);
+                return Err(format!("{rel_path}: {e}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        for (tmp, _, _) in &self.files {
+            let _ = fs::remove_file(tmp);
+        }
+    }
+}
+
+/// Replace a file through a synced temp file in the same folder and a rename, so Obsidian
+/// (or a crash) sees either the old note or the new one, never a partial write.
+///
+/// The temp name starts with a dot, which vault walks and watchers ignore.
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let tmp = write_temp(path, content, None)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+/// Write `content` to a synced temp file next to `path`, with the permissions of the file it
+/// will replace, or of `source` when `path` does not exist yet.
+fn write_temp(path: &Path, content: &str, source: Option<&Path>) -> io::Result<PathBuf> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{name}.{}.tmp", std::process::id()));
+    let result = File::create(&tmp).and_then(|mut file| {
+        let meta = fs::metadata(path).or_else(|e| source.map_or(Err(e), fs::metadata));
+        if let Ok(meta) = meta {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    match result {
+        Ok(()) => Ok(tmp),
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            Err(e)
+        }
+    }
+}
+
+/// Plan renaming the frontmatter key `from` to `to` across the vault.
+///
+/// Only notes matching every filter expression are touched (e.g. `file.inFolder("Projects")`).
+/// Nothing is written: the edits come back for review or `apply`. Fails without any edits
+/// when a filter does not parse or a note already has both keys.
+pub fn rename_property(
+    vault_root: &Path,
+    from: &str,
+    to: &str,
+    filters: &[String],
+) -> Result<Vec<FileEdit>, String> {
+    let filters = filters
+        .iter()
+        .map(|f| Expression::parse(f).map_err(|e| e.caret(f)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let notes = note::walk_vault(vault_root);
+    let this_ctx = ThisContext::default();
+    let eval_ctx = EvalContext::new(&this_ctx, &notes);
+
+    let mut edits = Vec::new();
+    let mut conflicts = Vec::new();
+    for ctx in &notes {
+        if !ctx.properties.contains_key(from) || !filters.iter().all(|f| f.matches(ctx, &eval_ctx))
+        {
+            continue;
+        }
+        let mut frontmatter =
+            Frontmatter::parse(ctx.content()).map_err(|e| format!("{}: {e}", ctx.rel_path))?;
+        match frontmatter.rename(from, to) {
+            Ok(true) if from != to => edits.push(FileEdit {
+                after: frontmatter.render(),
+                before: ctx.content().to_owned(),
+                path: ctx.path.clone(),
+                rel_path: ctx.rel_path.clone(),
+            }),
+            Ok(_) => {}
+            Err(_) => conflicts.push(ctx.rel_path.clone()),
+        }
+    }
+
+    if !conflicts.is_empty() {
+        conflicts.sort();
+        return Err(format!(
+            "`{to}` already exists in: {}",
+            conflicts.join(", ")
+        ));
+    }
+    edits.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    Ok(edits)
+}
+
+// ─── Links ──────────────────────────────────────────────────
+
+/// `[[target#sub|alias]]` and `![[…]]`: groups are the embed marker, target, subpath, alias.
+static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(!?)\[\[([^\]|#^\n]*)([#^][^\]|\n]*)?(\|[^\]\n]*)?\]\]").unwrap()
+});
+/// `[text](Note%20Name.md#sub)` and `![](<Note Name.md>)`: groups are everything up to the
+/// target, then the target in `<…>` or percent-encoded form, then the subpath.
+static MD_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(!?\[[^\]\n]*\]\()(?:<([^>#\n]+)>|([^)#\s<]+))(#[^)\s]*)?\)").unwrap()
+});
+static INLINE_CODE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`\n]*`").unwrap());
+
+/// How Obsidian writes new links (`newLinkFormat` in `.obsidian/app.json`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkFormat {
+    /// The bare name when it is unique in the vault, otherwise the full path.
+    #[default]
+    Shortest,
+    /// A path relative to the linking note's folder.
+    Relative,
+    /// The full vault path.
+    Absolute,
+}
+
+impl LinkFormat {
+    /// Read the vault's setting; a missing or unreadable `app.json` means `Shortest`.
+    pub fn from_vault(vault_root: &Path) -> Self {
+        let setting = fs::read_to_string(vault_root.join(".obsidian/app.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+            .and_then(|v| v.get("newLinkFormat")?.as_str().map(str::to_owned));
+        match setting.as_deref() {
+            Some("relative") => Self::Relative,
+            Some("absolute") => Self::Absolute,
+            _ => Self::Shortest,
+        }
+    }
+}
+
+/// Vault-relative path of the daily note for `date`, from the Daily notes core plugin
+/// settings (`.obsidian/daily-notes.json`: `folder` and a Moment.js `format`).
+///
+/// Defaults to `YYYY-MM-DD.md` in the vault root, as Obsidian does.
+pub fn daily_note_path(vault_root: &Path, date: chrono::NaiveDate) -> String {
+    let settings = fs::read_to_string(vault_root.join(".obsidian/daily-notes.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok());
+    let setting = |key: &str| {
+        settings
+            .as_ref()
+            .and_then(|v| v.get(key)?.as_str())
+            .map(|s| s.trim().trim_matches('/').to_owned())
+            .filter(|s| !s.is_empty())
+    };
+    let format = setting("format").unwrap_or_else(|| "YYYY-MM-DD".to_owned());
+    let name = date.format(&moment_to_strftime(&format)).to_string();
+    match setting("folder") {
+        Some(folder) => format!("{folder}/{name}.md"),
+        None => format!("{name}.md"),
+    }
+}
+
+/// Translate the Moment.js date tokens daily-note formats use into `strftime`.
+fn moment_to_strftime(format: &str) -> String {
+    const TOKENS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("YY", "%y"),
+        ("MMMM", "%B"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("M", "%-m"),
+        ("DD", "%d"),
+        ("D", "%-d"),
+        ("dddd", "%A"),
+        ("ddd", "%a"),
+        ("ww", "%V"),
+    ];
+    let mut out = String::new();
+    let mut rest = format;
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            // `[literal]` text is copied as-is
+            let end = rest.find(']').unwrap_or(rest.len());
+            out.push_str(&rest[1..end].replace('%', "%%"));
+            rest = &rest[(end + 1).min(rest.len())..];
+        } else if let Some((token, spec)) = TOKENS.iter().find(|(t, _)| rest.starts_with(t)) {
+            out.push_str(spec);
+            rest = &rest[token.len()..];
+        } else {
+            if c == '%' {
+                out.push('%');
+            }
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Resolves link targets to vault files the way Obsidian does.
+#[derive(Debug, Clone)]
+pub struct LinkIndex {
+    /// Vault-relative paths of every file outside hidden folders.
+    files: Vec<String>,
+}
+
+impl LinkIndex {
+    pub fn build(vault_root: &Path) -> Self {
+        let files = WalkDir::new(vault_root)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let rel = e.path().strip_prefix(vault_root).ok()?;
+                Some(rel.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// An index over the given vault-relative paths, e.g. the notes already loaded.
+    pub fn from_paths(files: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            files: files.into_iter().collect(),
+        }
+    }
+
+    /// The file a link in `source` points to.
+    ///
+    /// Tries the target as a vault path, then relative to `source`'s folder, then as a name
+    /// anywhere in the vault, preferring `source`'s folder and then the shortest path.
+    /// Matching is case-insensitive and `.md` is optional.
+    pub fn resolve(&self, target: &str, source: &str) -> Option<&str> {
+        let target = target.trim().trim_start_matches('/').to_lowercase();
+        if target.is_empty() {
+            return None;
+        }
+        let names = |f: &String| {
+            let lower = f.to_lowercase();
+            let bare = lower.strip_suffix(".md").map(str::to_owned);
+            std::iter::once(lower).chain(bare)
+        };
+        let relative = normalize_path(&format!("{}/{target}", folder_of(source).to_lowercase()));
+        if let Some(found) = self
+            .files
+            .iter()
+            .find(|f| names(f).any(|n| n == target || n == relative))
+        {
+            return Some(found);
+        }
+        let suffix = format!("/{target}");
+        self.files
+            .iter()
+            .filter(|f| names(f).any(|n| n.ends_with(&suffix)))
+            .min_by_key(|f| (folder_of(f) != folder_of(source), f.len(), f.as_str()))
+            .map(String::as_str)
+    }
+
+    /// Link text for `path` written from `source` in the given format.
+    pub fn link_text(&self, path: &str, source: &str, format: LinkFormat) -> String {
+        let link = path.strip_suffix(".md").unwrap_or(path);
+        match format {
+            LinkFormat::Absolute => link.to_owned(),
+            LinkFormat::Relative => relative_path(folder_of(source), link),
+            LinkFormat::Shortest => {
+                let name = basename(link);
+                let same_name = self
+                    .files
+                    .iter()
+                    .filter(|f| {
+                        basename(f.strip_suffix(".md").unwrap_or(f)).to_lowercase()
+                            == name.to_lowercase()
+                    })
+                    .count();
+                if same_name <= 1 {
+                    name.to_owned()
+                } else {
+                    link.to_owned()
+                }
+            }
+        }
+    }
+
+    /// The index after moving `from` to `to`.
+    fn moved(&self, from: &str, to: &str) -> Self {
+        let files = self
+            .files
+            .iter()
+            .map(|f| if f == from { to.to_owned() } else { f.clone() })
+            .collect();
+        Self { files }
+    }
+}
+
+fn folder_of(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(folder, _)| folder)
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Collapse `.` and `..` segments.
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// `target` relative to `folder`, both vault-relative.
+fn relative_path(folder: &str, target: &str) -> String {
+    let from: Vec<&str> = folder.split('/').filter(|p| !p.is_empty()).collect();
+    let to: Vec<&str> = target.split('/').collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let ups = std::iter::repeat_n("..", from.len() - common);
+    ups.chain(to[common..].iter().copied())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// ─── Rename ─────────────────────────────────────────────────
+
+/// One link that a rename rewrites.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRewrite {
+    pub file: String,
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// A planned note move with the link rewrites it needs.
+#[derive(Debug, Clone)]
+pub struct RenamePlan {
+    pub from: String,
+    pub to: String,
+    pub rewrites: Vec<LinkRewrite>,
+    pub edits: Vec<FileEdit>,
+    vault_root: PathBuf,
+}
+
+impl RenamePlan {
+    /// Human-readable summary: the move, then every rewritten link as `file:line`.
+    pub fn report(&self) -> String {
+        let files = self
+            .rewrites
+            .iter()
+            .map(|r| r.file.as_str())
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut out = format!(
+            "{} → {} ({} link(s) in {} note(s))\n",
+            self.from,
+            self.to,
+            self.rewrites.len(),
+            files.len()
+        );
+        for r in &self.rewrites {
+            let _ = writeln!(out, "  {}:{}: {} → {}", r.file, r.line, r.before, r.after);
+        }
+        out
+    }
+
+    /// Stage the rewritten notes, move the file, then put the rewrites in place. Nothing is
+    /// moved when staging fails.
+    pub fn apply(&self) -> Result<(), String> {
+        let dest = self.vault_root.join(&self.to);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", self.to))?;
+        }
+        let source = self.vault_root.join(&self.from);
+        let staged = stage(&self.edits, Some((&dest, &source)))?;
+        fs::rename(&source, &dest).map_err(|e| format!("{} → {}: {e}", self.from, self.to))?;
+        staged.commit()
+    }
+}
+
+/// Plan moving `from` to `to` (vault-relative paths) and rewriting every link to it:
+/// `[[Old]]`, `[[Old#Heading|alias]]`, `![[Old]]`, `[text](Old%20Name.md)` and wikilinks in
+/// frontmatter. Links inside code blocks and inline code are left alone.
+///
+/// New link text follows the vault's `newLinkFormat`; headings, block references and
+/// aliases are kept. A `to` without an extension gets the one from `from`. With the
+/// relative format, links inside the moved note are rewritten for its new folder too.
+pub fn rename(vault_root: &Path, from: &str, to: &str) -> Result<RenamePlan, String> {
+    let from = normalize_path(from);
+    let mut to = normalize_path(to);
+    if Path::new(&to).extension().is_none() {
+        if let Some(ext) = Path::new(&from).extension() {
+            to = format!("{to}.{}", ext.to_string_lossy());
+        }
+    }
+    if !vault_root.join(&from).is_file() {
+        return Err(format!("no such note: {from}"));
+    }
+    if vault_root.join(&to).exists() {
+        return Err(format!("{to} already exists"));
+    }
+
+    let before = LinkIndex::build(vault_root);
+    let relink = Relink {
+        after: before.moved(&from, &to),
+        before,
+        format: LinkFormat::from_vault(vault_root),
+        from: &from,
+        to: &to,
+    };
+
+    let mut rewrites = Vec::new();
+    let mut edits = Vec::new();
+    for ctx in note::walk_vault(vault_root) {
+        let source = if ctx.rel_path == from {
+            &to
+        } else {
+            &ctx.rel_path
+        };
+        let (content, file_rewrites) = relink.note(&ctx, source);
+        if !file_rewrites.is_empty() {
+            edits.push(FileEdit {
+                path: vault_root.join(source),
+                rel_path: source.clone(),
+                after: content,
+                before: ctx.content().to_owned(),
+            });
+            rewrites.extend(file_rewrites);
+        }
+    }
+
+    rewrites.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    edits.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    Ok(RenamePlan {
+        from,
+        to,
+        rewrites,
+        edits,
+        vault_root: vault_root.to_path_buf(),
+    })
+}
+
+/// The link rewriting side of a rename.
+struct Relink<'a> {
+    before: LinkIndex,
+    after: LinkIndex,
+    format: LinkFormat,
+    from: &'a str,
+    to: &'a str,
+}
+
+impl Relink<'_> {
+    /// Rewrite the links in one note (written at `source` after the move), returning the new
+    /// content and each rewrite.
+    fn note(&self, ctx: &NoteContext, source: &str) -> (String, Vec<LinkRewrite>) {
+        let content = ctx.content();
+        let code = code_ranges(content);
+        let outside_code = |m: &regex::Match| !code.iter().any(|r| r.contains(&m.start()));
+
+        let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+        for caps in LINK_RE.captures_iter(content) {
+            let whole = caps.get(0).unwrap();
+            let Some(target) = outside_code(&whole)
+                .then(|| self.new_target(&caps[2], &ctx.rel_path, source))
+                .flatten()
+            else {
+                continue;
+            };
+            let text = format!(
+                "{}[[{}{}{}]]",
+                &caps[1],
+                self.after.link_text(target, source, self.format),
+                caps.get(3).map_or("", |m| m.as_str()),
+                caps.get(4).map_or("", |m| m.as_str()),
+            );
+            replacements.push((whole.range(), text));
+        }
+        for caps in MD_LINK_RE.captures_iter(content) {
+            let whole = caps.get(0).unwrap();
+            let (raw, bracketed) = match (caps.get(2), caps.get(3)) {
+                (Some(m), _) => (m.as_str().to_owned(), true),
+                (None, Some(m)) => (percent_decode(m.as_str()), false),
+                (None, None) => continue,
+            };
+            let overlaps = replacements.iter().any(|(r, _)| r.contains(&whole.start()));
+            if overlaps || raw.contains("://") || !outside_code(&whole) {
+                continue;
+            }
+            let Some(target) = self.new_target(&raw, &ctx.rel_path, source) else {
+                continue;
+            };
+            let mut link = self.after.link_text(target, source, self.format);
+            if target.strip_suffix(".md").is_some() {
+                link.push_str(".md");
+            }
+            let link = if bracketed {
+                format!("<{link}>")
+            } else {
+                link.replace(' ', "%20")
+            };
+            let subpath = caps.get(4).map_or("", |m| m.as_str());
+            replacements.push((whole.range(), format!("{}{link}{subpath})", &caps[1])));
+        }
+        replacements.sort_by_key(|(r, _)| r.start);
+
+        let mut out = String::with_capacity(content.len());
+        let mut rewrites = Vec::new();
+        let mut last = 0;
+        for (range, text) in replacements {
+            let before = &content[range.clone()];
+            if text != before {
+                rewrites.push(LinkRewrite {
+                    file: source.to_owned(),
+                    line: content[..range.start].matches('\n').count() + 1,
+                    before: before.to_owned(),
+                    after: text.clone(),
+                });
+            }
+            out.push_str(&content[last..range.start]);
+            out.push_str(&text);
+            last = range.end;
+        }
+        out.push_str(&content[last..]);
+        (out, rewrites)
+    }
+
+    /// Where a link in `rel_path` should point after the move, if it needs rewriting: links to
+    /// the moved note, and with the relative format every link inside the moved note.
+    fn new_target<'s>(&'s self, link: &str, rel_path: &str, source: &str) -> Option<&'s str> {
+        let target = self.before.resolve(link, rel_path)?;
+        if target == self.from {
+            Some(self.to)
+        } else if rel_path != source && self.format == LinkFormat::Relative {
+            Some(target)
+        } else {
+            None
+        }
+    }
+}
+
+/// Byte ranges of fenced code blocks and inline code spans, where links are only text.
+fn code_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut line_starts = vec![0];
+    line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+    let offset = |line: usize| line_starts.get(line - 1).copied().unwrap_or(content.len());
+
+    let mut ranges: Vec<Range<usize>> = Structure::parse(content)
+        .code_blocks
+        .iter()
+        .map(|block| offset(block.line)..offset(block.end + 1))
+        .collect();
+    let spans: Vec<Range<usize>> = INLINE_CODE_RE
+        .find_iter(content)
+        .map(|m| m.range())
+        .filter(|span| !ranges.iter().any(|r| r.contains(&span.start)))
+        .collect();
+    ranges.extend(spans);
+    ranges
+}
+
+/// Decode `%XX` escapes in a Markdown link target; invalid escapes stay as written.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_owned())
+}
@@ -24,3 +24,302 @@ fn returns_none_without_obsidian_dir() {
 
     assert_eq!(find_vault(file.to_str().unwrap()), None);
 }
+
+fn make_vault(notes: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join(".obsidian")).unwrap();
+    for (rel, content) in notes {
+        let path = dir.path().join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn rename_property_plans_edits_for_matching_notes() {
+    let dir = make_vault(&[
+        ("Projects/A.md", "---\nstatus: active # keep\n---\nBody\n"),
+        ("Projects/B.md", "---\ntitle: B\n---\n"),
+        ("Areas/C.md", "---\nstatus: done\n---\n"),
+    ]);
+    let filters = vec![r#"file.inFolder("Projects")"#.to_owned()];
+    let edits = rename_property(dir.path(), "status", "project.status", &filters).unwrap();
+
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].rel_path, "Projects/A.md");
+    assert_eq!(
+        edits[0].after,
+        "---\nproject.status: active # keep\n---\nBody\n"
+    );
+    assert!(edits[0]
+        .diff()
+        .contains("--- a/Projects/A.md\n+++ b/Projects/A.md\n"));
+    assert!(edits[0]
+        .diff()
+        .contains("-status: active # keep\n+project.status: active # keep\n"));
+
+    // Planning writes nothing; applying does
+    let path = dir.path().join("Projects/A.md");
+    assert_eq!(fs::read_to_string(&path).unwrap(), edits[0].before);
+    apply(&edits).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), edits[0].after);
+    assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 2);
+}
+
+#[test]
+fn rename_property_refuses_conflicts_and_bad_filters() {
+    let dir = make_vault(&[
+        ("A.md", "---\nstatus: a\nproject.status: b\n---\n"),
+        ("B.md", "---\nstatus: c\n---\n"),
+    ]);
+    let err = rename_property(dir.path(), "status", "project.status", &[]).unwrap_err();
+    assert_eq!(err, "`project.status` already exists in: A.md");
+
+    let err = rename_property(dir.path(), "status", "x", &["file.name ==".to_owned()]).unwrap_err();
+    assert!(err.contains("unexpected end of expression"));
+}
+
+#[test]
+fn link_index_resolves_like_obsidian() {
+    let dir = make_vault(&[
+        ("Projects/Alpha.md", ""),
+        ("Archive/Alpha.md", ""),
+        ("Archive/Beta.md", ""),
+        ("Assets/diagram.png", ""),
+    ]);
+    let index = LinkIndex::build(dir.path());
+    assert_eq!(
+        index.resolve("Archive/Alpha", "x.md"),
+        Some("Archive/Alpha.md")
+    );
+    assert_eq!(
+        index.resolve("alpha", "Archive/Beta.md"),
+        Some("Archive/Alpha.md")
+    );
+    assert_eq!(
+        index.resolve("Beta.md", "Projects/Alpha.md"),
+        Some("Archive/Beta.md")
+    );
+    assert_eq!(
+        index.resolve("../Archive/Beta", "Projects/Alpha.md"),
+        Some("Archive/Beta.md")
+    );
+    assert_eq!(
+        index.resolve("diagram.png", "x.md"),
+        Some("Assets/diagram.png")
+    );
+    assert_eq!(index.resolve("Gamma", "x.md"), None);
+
+    assert_eq!(
+        index.link_text("Archive/Beta.md", "x.md", LinkFormat::Shortest),
+        "Beta"
+    );
+    assert_eq!(
+        index.link_text("Archive/Alpha.md", "x.md", LinkFormat::Shortest),
+        "Archive/Alpha"
+    );
+    assert_eq!(
+        index.link_text("Archive/Beta.md", "Projects/Alpha.md", LinkFormat::Relative),
+        "../Archive/Beta"
+    );
+    assert_eq!(
+        index.link_text("Assets/diagram.png", "x.md", LinkFormat::Absolute),
+        "Assets/diagram.png"
+    );
+}
+
+#[test]
+fn link_format_reads_app_json() {
+    let dir = make_vault(&[]);
+    assert_eq!(LinkFormat::from_vault(dir.path()), LinkFormat::Shortest);
+    fs::write(
+        dir.path().join(".obsidian/app.json"),
+        r#"{"newLinkFormat": "absolute"}"#,
+    )
+    .unwrap();
+    assert_eq!(LinkFormat::from_vault(dir.path()), LinkFormat::Absolute);
+}
+
+#[test]
+fn rename_rewrites_links_keeping_headings_and_aliases() {
+    let dir = make_vault(&[
+        ("Notes/Old.md", "---\nup: \"[[Index]]\"\n---\n# Old\n"),
+        (
+            "Index.md",
+            "---\nrelated:\n  - \"[[Old]]\"\n---\nSee [[Old]], [[Notes/Old#Intro|the intro]]\nand ![[old]] but not [[Older]].\n",
+        ),
+    ]);
+    let plan = rename(dir.path(), "Notes/Old.md", "Archive/New").unwrap();
+    assert_eq!(plan.to, "Archive/New.md");
+    assert_eq!(
+        plan.rewrites
+            .iter()
+            .map(|r| (r.line, r.before.as_str(), r.after.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (3, "[[Old]]", "[[New]]"),
+            (5, "[[Old]]", "[[New]]"),
+            (
+                5,
+                "[[Notes/Old#Intro|the intro]]",
+                "[[New#Intro|the intro]]"
+            ),
+            (6, "![[old]]", "![[New]]"),
+        ]
+    );
+    assert!(plan.report().starts_with(
+        "Notes/Old.md → Archive/New.md (4 link(s) in 1 note(s))\n  Index.md:3: [[Old]] → [[New]]\n"
+    ));
+
+    plan.apply().unwrap();
+    assert!(!dir.path().join("Notes/Old.md").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Archive/New.md")).unwrap(),
+        "---\nup: \"[[Index]]\"\n---\n# Old\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Index.md")).unwrap(),
+        "---\nrelated:\n  - \"[[New]]\"\n---\nSee [[New]], [[New#Intro|the intro]]\nand ![[New]] but not [[Older]].\n"
+    );
+}
+
+#[test]
+fn rename_with_relative_links_updates_the_moved_note() {
+    let dir = make_vault(&[
+        ("A/Note.md", "Link to [[../B/Other]].\n"),
+        ("B/Other.md", "Back to [[../A/Note]].\n"),
+    ]);
+    fs::write(
+        dir.path().join(".obsidian/app.json"),
+        r#"{"newLinkFormat": "relative"}"#,
+    )
+    .unwrap();
+    let plan = rename(dir.path(), "A/Note.md", "C/D/Note.md").unwrap();
+    plan.apply().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("C/D/Note.md")).unwrap(),
+        "Link to [[../../B/Other]].\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("B/Other.md")).unwrap(),
+        "Back to [[../C/D/Note]].\n"
+    );
+}
+
+#[test]
+fn rename_rewrites_markdown_links_and_skips_code() {
+    let dir = make_vault(&[
+        ("Notes/Old Name.md", "# Old\n"),
+        (
+            "Index.md",
+            "See [the intro](Notes/Old%20Name.md#Intro) and ![](<Old Name.md>).\n`[[Old Name]]` and [web](https://x.org/Old%20Name.md)\n```\n[[Old Name]]\n```\n[[Old Name]]\n",
+        ),
+    ]);
+    let plan = rename(dir.path(), "Notes/Old Name.md", "New Name").unwrap();
+    assert_eq!(plan.rewrites.len(), 3);
+    plan.apply().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("Index.md")).unwrap(),
+        "See [the intro](New%20Name.md#Intro) and ![](<New Name.md>).\n`[[Old Name]]` and [web](https://x.org/Old%20Name.md)\n```\n[[Old Name]]\n```\n[[New Name]]\n"
+    );
+}
+
+#[test]
+fn apply_writes_nothing_when_staging_fails() {
+    let dir = make_vault(&[("A.md", "old\n")]);
+    let edit = |rel: &str| FileEdit {
+        path: dir.path().join(rel),
+        rel_path: rel.to_owned(),
+        before: "old\n".into(),
+        after: "new\n".into(),
+    };
+    let err = apply(&[edit("A.md"), edit("Missing/B.md")]).unwrap_err();
+    assert!(err.starts_with("Missing/B.md: "));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("A.md")).unwrap(),
+        "old\n"
+    );
+    let names: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(names.len(), 2, "temp files left behind: {names:?}");
+}
+
+#[cfg(unix)]
+#[test]
+fn write_atomic_keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("Script.md");
+    fs::write(&path, "old").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+    write_atomic(&path, "new").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    assert_eq!(
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+        0o640
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn rename_keeps_the_moved_note_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = make_vault(&[
+        ("A/Note.md", "Link to [[../B/Other]].\n"),
+        ("B/Other.md", "# Other\n"),
+    ]);
+    fs::write(
+        dir.path().join(".obsidian/app.json"),
+        r#"{"newLinkFormat": "relative"}"#,
+    )
+    .unwrap();
+    let source = dir.path().join("A/Note.md");
+    fs::set_permissions(&source, fs::Permissions::from_mode(0o640)).unwrap();
+    let plan = rename(dir.path(), "A/Note.md", "C/D/Note.md").unwrap();
+    assert_eq!(plan.edits[0].rel_path, "C/D/Note.md");
+    plan.apply().unwrap();
+    assert_eq!(
+        fs::metadata(dir.path().join("C/D/Note.md"))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o777,
+        0o640
+    );
+}
+
+#[test]
+fn rename_refuses_missing_sources_and_existing_targets() {
+    let dir = make_vault(&[("A.md", ""), ("B.md", "")]);
+    assert_eq!(
+        rename(dir.path(), "C.md", "D.md").unwrap_err(),
+        "no such note: C.md"
+    );
+    assert_eq!(
+        rename(dir.path(), "A.md", "B").unwrap_err(),
+        "B.md already exists"
+    );
+}
+
+#[test]
+fn daily_note_path_follows_plugin_settings() {
+    let dir = tempdir().unwrap();
+    fs::create_dir_all(dir.path().join(".obsidian")).unwrap();
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+    assert_eq!(daily_note_path(dir.path(), date), "2026-03-07.md");
+
+    fs::write(
+        dir.path().join(".obsidian/daily-notes.json"),
+        r#"{"folder": "Resources/Journals/Daily/", "format": "YYYY/MM/YYYY-MM-DD [Daily]"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        daily_note_path(dir.path(), date),
+        "Resources/Journals/Daily/2026/03/2026-03-07 Daily.md"
+    );
+}
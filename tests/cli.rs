@@ -0,0 +1,873 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn fixture(rel: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/vault")
+        .join(rel)
+}
+
+/// Copy the fixture vault so a test can modify it.
+fn scratch_vault() -> tempfile::TempDir {
+    let src = fixture("");
+    let dir = tempfile::tempdir().unwrap();
+    for entry in walkdir::WalkDir::new(&src) {
+        let entry = entry.unwrap();
+        let target = dir.path().join(entry.path().strip_prefix(&src).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    dir
+}
+
+fn obsidian_base() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("obsidian-base")
+}
+
+fn obsidian_note() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("obsidian-note")
+}
+
+#[test]
+fn emits_jsonl_per_view() {
+    let output = obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0]["view"], "All");
+    assert_eq!(lines[0]["name"], "Designing Data-Intensive Applications");
+    assert_eq!(lines[3]["view"], "Favourites");
+    assert_eq!(lines[3]["author"], "[[Andrew Hunt]]");
+}
+
+#[test]
+fn paths_for_single_view() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--view", "favourites", "--paths"])
+        .assert()
+        .success()
+        .stdout("Library/Books/The Pragmatic Programmer.md\n");
+}
+
+#[test]
+fn missing_base_file_fails() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Missing.base"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot resolve"));
+}
+
+#[test]
+fn markdown_table_for_view() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--view", "Favourites", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout("| Title | Author |\n| --- | --- |\n| [[The Pragmatic Programmer]] | [[Andrew Hunt]] |\n");
+}
+
+#[test]
+fn csv_uses_order_columns() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--view", "All", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Title,rating,finished\nDesigning Data-Intensive Applications,4,2025-06-10\n",
+        ));
+}
+
+#[test]
+fn unknown_format_fails() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--format", "xml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown format"));
+}
+
+#[test]
+fn ad_hoc_query() {
+    obsidian_base()
+        .args(["query", "--vault"])
+        .arg(fixture(""))
+        .args([
+            "--filter",
+            r#"file.hasTag("type/item/book")"#,
+            "--filter",
+            "rating >= 4",
+            "--sort",
+            "finished:desc",
+            "--columns",
+            "file.name,rating",
+            "--format",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "name,rating\nDesigning Data-Intensive Applications,4\nThe Pragmatic Programmer,5\n",
+        );
+}
+
+#[test]
+fn query_without_filters_lists_vault() {
+    let output = obsidian_base()
+        .args(["query", "--paths", "--vault"])
+        .arg(fixture(""))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 5);
+}
+
+#[test]
+fn query_rejects_malformed_filters() {
+    obsidian_base()
+        .args(["query", "--filter", "rating >== 4", "--vault"])
+        .arg(fixture(""))
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("^"));
+}
+
+#[test]
+fn explain_single_note() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--explain", "--note", "Projects/Alpha/Alpha.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Projects/Alpha/Alpha.md: rejected by top-level filters\n",
+        ))
+        .stdout(predicate::str::contains(
+            "✗ file.hasTag(\"type/item/book\") → false",
+        ));
+}
+
+#[test]
+fn explain_unknown_note_fails() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .args(["--explain", "--note", "Nope"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn explain_rejects_output_and_watch_flags() {
+    for extra in [
+        &["--watch"][..],
+        &["--snapshot", "snap.jsonl"],
+        &["--diff", "snap.jsonl"],
+        &["--format", "csv"],
+        &["--paths"],
+    ] {
+        obsidian_base()
+            .arg(fixture("Library/Books/Reading.base"))
+            .arg("--explain")
+            .args(extra)
+            .assert()
+            .code(1)
+            .stdout("")
+            .stderr(predicate::str::contains(
+                "cannot be combined with --explain",
+            ));
+    }
+}
+
+#[test]
+fn repl_evaluates_against_selected_note() {
+    obsidian_base()
+        .args(["repl", "--vault"])
+        .arg(fixture(""))
+        .write_stdin(
+            ":note Clean Code\nfile.name\nfile.name.startsWith(\"Cl\"\n:filter rating >= 5\n",
+        )
+        .assert()
+        .success()
+        .stdout(format!(
+            "Selected Library/Books/Clean Code.md\n\"Clean Code\"\n\
+             file.name.startsWith(\"Cl\"\n{}^ expected `)`\n\
+             Library/Books/The Pragmatic Programmer.md\n(1 notes)\n",
+            " ".repeat(25)
+        ));
+}
+
+#[test]
+fn repl_requires_a_note_for_expressions() {
+    obsidian_base()
+        .args(["repl", "--vault"])
+        .arg(fixture(""))
+        .write_stdin("file.name\n")
+        .assert()
+        .success()
+        .stdout("No note selected (use :note <path>)\n");
+}
+
+#[test]
+fn repl_matches_whole_command_words() {
+    obsidian_base()
+        .args(["repl", "--vault"])
+        .arg(fixture(""))
+        .write_stdin(":notes Clean Code\n:filterx rating\n:note\tClean Code\n")
+        .assert()
+        .success()
+        .stdout(
+            "Unknown command: :notes (try :help)\nUnknown command: :filterx (try :help)\n\
+             Selected Library/Books/Clean Code.md\n",
+        );
+}
+
+#[test]
+fn snapshot_then_diff() {
+    let vault = scratch_vault();
+    let base_file = vault.path().join("Library/Books/Reading.base");
+    let snap = vault.path().join("reading.jsonl");
+
+    obsidian_base()
+        .arg(&base_file)
+        .args(["--view", "All", "--snapshot"])
+        .arg(&snap)
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&snap).unwrap().lines().count(), 3);
+
+    std::fs::remove_file(vault.path().join("Library/Books/Clean Code.md")).unwrap();
+    let dune = vault.path().join("Library/Books/Dune.md");
+    std::fs::write(&dune, "---\ntags: [type/item/book]\nrating: 5\n---\n").unwrap();
+    let ddia = vault
+        .path()
+        .join("Library/Books/Designing Data-Intensive Applications.md");
+    let text = std::fs::read_to_string(&ddia).unwrap();
+    std::fs::write(&ddia, text.replace("rating: 4", "rating: 5")).unwrap();
+
+    let output = obsidian_base()
+        .arg(&base_file)
+        .args(["--view", "All", "--diff"])
+        .arg(&snap)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let changes: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    let summary: Vec<(&str, &str)> = changes
+        .iter()
+        .map(|c| (c["change"].as_str().unwrap(), c["name"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("changed", "Designing Data-Intensive Applications"),
+            ("added", "Dune"),
+            ("removed", "Clean Code"),
+        ]
+    );
+    assert_eq!(changes[0]["changes"]["rating"]["old"], 4);
+}
+
+#[test]
+fn diff_against_missing_snapshot_adds_everything() {
+    let vault = scratch_vault();
+    let output = obsidian_base()
+        .arg(vault.path().join("Library/Books/Reading.base"))
+        .args(["--view", "Favourites", "--diff"])
+        .arg(vault.path().join("none.jsonl"))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains(r#""change":"added""#));
+}
+
+#[test]
+fn diff_honors_json_and_rejects_table_formats() {
+    let vault = scratch_vault();
+    let base_file = vault.path().join("Library/Books/Reading.base");
+    let output = obsidian_base()
+        .arg(&base_file)
+        .args(["--view", "Favourites", "--format", "json", "--diff"])
+        .arg(vault.path().join("none.jsonl"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let changes: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(changes.as_array().unwrap().len(), 1);
+    assert_eq!(changes[0]["change"], "added");
+
+    obsidian_base()
+        .arg(&base_file)
+        .args(["--format", "csv", "--diff"])
+        .arg(vault.path().join("none.jsonl"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "--diff emits row changes as jsonl or json",
+        ));
+}
+
+// ─── obsidian-note ──────────────────────────────────────────
+
+#[test]
+fn prop_set_and_remove_edit_in_place() {
+    let vault = scratch_vault();
+    let note = vault.path().join("Projects/Alpha/Alpha.md");
+    obsidian_note()
+        .args(["prop", "set"])
+        .arg(&note)
+        .args(["project.status", "done"])
+        .assert()
+        .success();
+    obsidian_note()
+        .args(["prop", "remove"])
+        .arg(&note)
+        .arg("project.priority")
+        .assert()
+        .success();
+    obsidian_note()
+        .args(["prop", "append"])
+        .arg(&note)
+        .args(["related", "[[Clean Code]]"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&note).unwrap();
+    assert_eq!(
+        content,
+        "---\ntags:\n  - type/project\nproject.status: done\nrelated:\n  - \"[[Clean Code]]\"\n---\n# Alpha\n\nReading [[Clean Code]] for the refactor.\n"
+    );
+}
+
+#[test]
+fn prop_set_keeps_text_values_whole() {
+    let vault = scratch_vault();
+    let note = vault.path().join("Projects/Alpha/Alpha.md");
+    for (key, value) in [
+        ("title", "Meeting: Q3"),
+        ("note", "see #3"),
+        ("raw", "{a: 1}"),
+        ("rating", "4"),
+        ("done", "true"),
+        ("aliases", "[A, 2]"),
+        ("pairs", "[a: 1]"),
+    ] {
+        obsidian_note()
+            .args(["prop", "set"])
+            .arg(&note)
+            .args([key, value])
+            .assert()
+            .success();
+    }
+
+    let content = std::fs::read_to_string(&note).unwrap();
+    assert!(content.contains(
+        "title: \"Meeting: Q3\"\nnote: \"see #3\"\nraw: \"{a: 1}\"\nrating: 4\ndone: true\naliases:\n  - A\n  - 2\npairs: \"[a: 1]\"\n"
+    ));
+}
+
+#[test]
+fn prop_rename_of_missing_key_fails() {
+    let vault = scratch_vault();
+    let note = vault.path().join("Projects/Alpha/Alpha.md");
+    obsidian_note()
+        .args(["prop", "rename"])
+        .arg(&note)
+        .args(["status", "project.status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no property `status`"));
+}
+
+// ─── obsidian-vault ─────────────────────────────────────────
+
+fn obsidian_vault() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("obsidian-vault")
+}
+
+#[test]
+fn rename_prop_dry_run_prints_diff_without_writing() {
+    let vault = scratch_vault();
+    let before = std::fs::read_to_string(vault.path().join("Library/Books/Clean Code.md")).unwrap();
+    obsidian_vault()
+        .args([
+            "rename-prop",
+            "rating",
+            "book.rating",
+            "--dry-run",
+            "--vault",
+        ])
+        .arg(vault.path())
+        .args(["--filter", r#"file.name.startsWith("Clean")"#])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "--- a/Library/Books/Clean Code.md\n+++ b/Library/Books/Clean Code.md\n",
+        ))
+        .stdout(predicate::str::contains("-rating: 2\n+book.rating: 2\n"))
+        .stderr(predicate::str::contains("in 1 note(s)"));
+    let after = std::fs::read_to_string(vault.path().join("Library/Books/Clean Code.md")).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn rename_prop_rewrites_every_note() {
+    let vault = scratch_vault();
+    let output = obsidian_vault()
+        .args(["rename-prop", "rating", "book.rating", "--vault"])
+        .arg(vault.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+
+    let content =
+        std::fs::read_to_string(vault.path().join("Library/Books/Clean Code.md")).unwrap();
+    assert!(content.contains("\nbook.rating: 2\n"));
+}
+
+#[test]
+fn mv_rewrites_backlinks() {
+    let vault = scratch_vault();
+    obsidian_vault()
+        .args([
+            "mv",
+            "Library/Books/Clean Code.md",
+            "Archive/Clean Code (2008)",
+            "--vault",
+        ])
+        .arg(vault.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Projects/Alpha/Alpha.md:9: [[Clean Code]] → [[Clean Code (2008)]]",
+        ));
+    assert!(vault.path().join("Archive/Clean Code (2008).md").is_file());
+    let alpha = std::fs::read_to_string(vault.path().join("Projects/Alpha/Alpha.md")).unwrap();
+    assert!(alpha.contains("Reading [[Clean Code (2008)]] for the refactor."));
+}
+
+#[test]
+fn mv_dry_run_leaves_the_vault_alone() {
+    let vault = scratch_vault();
+    obsidian_vault()
+        .args([
+            "mv",
+            "Projects/Alpha/Alpha.md",
+            "Projects/Beta",
+            "--dry-run",
+            "--vault",
+        ])
+        .arg(vault.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Projects/Alpha/Alpha.md → Projects/Beta.md",
+        ));
+    assert!(vault.path().join("Projects/Alpha/Alpha.md").is_file());
+}
+
+#[test]
+fn check_passes_conforming_notes() {
+    obsidian_vault()
+        .arg("check")
+        .arg(fixture("Library/Books"))
+        .arg("--schema")
+        .arg(fixture("../schemas/book.mdschema"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 file(s), 3 passing, 0 failing"));
+}
+
+#[test]
+fn check_reports_violations_as_json() {
+    let pattern = fixture("Projects").join("**/*.md");
+    let output = obsidian_vault()
+        .arg("check")
+        .arg(pattern.to_str().unwrap())
+        .arg("--schema")
+        .arg(fixture("../schemas/book.mdschema"))
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let reports: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violations = &reports[0]["violations"];
+    assert_eq!(violations[0]["category"], "frontmatter");
+    assert_eq!(violations[0]["message"], "missing required field 'author'");
+    assert_eq!(violations[1]["message"], "missing required field 'rating'");
+}
+
+#[test]
+fn lint_passes_flat_vault() {
+    obsidian_vault()
+        .args(["lint", "--vault"])
+        .arg(fixture(""))
+        .assert()
+        .success()
+        .stdout("0 finding(s) in 0 note(s)\n");
+}
+
+#[test]
+fn lint_fix_flattens_nested_frontmatter() {
+    let vault = scratch_vault();
+    let note = vault.path().join("Nested.md");
+    std::fs::write(
+        &note,
+        "---\ntitle: Review: Q3\nproject:\n  status: active\n---\n# Nested\n",
+    )
+    .unwrap();
+
+    obsidian_vault()
+        .args(["lint", "--vault"])
+        .arg(vault.path())
+        .assert()
+        .code(1)
+        .stdout(predicate::str::starts_with(
+            "Nested.md:2: [unquoted-colon] value contains `:` and must be quoted (fixable)",
+        ));
+
+    obsidian_vault()
+        .args(["lint", "--fix", "--vault"])
+        .arg(vault.path())
+        .assert()
+        .success()
+        .stderr("Fixed Nested.md\n");
+    assert_eq!(
+        std::fs::read_to_string(&note).unwrap(),
+        "---\ntitle: \"Review: Q3\"\nproject.status: active\n---\n# Nested\n"
+    );
+}
+
+#[test]
+fn tasks_filters_with_task_binding() {
+    let vault = scratch_vault();
+    std::fs::create_dir_all(vault.path().join("Orchestration")).unwrap();
+    std::fs::write(
+        vault.path().join("Orchestration/Backlog.md"),
+        "# Backlog\n\n- [ ] Overdue 📅 2020-01-01\n- [ ] Later [due:: 2999-01-01]\n- [x] Shipped 📅 2020-01-01\n",
+    )
+    .unwrap();
+
+    let output = obsidian_base()
+        .args([
+            "tasks",
+            "--filter",
+            "!task.closed && task.due < today()",
+            "--vault",
+        ])
+        .arg(vault.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["file"], "Orchestration/Backlog.md");
+    assert_eq!(rows[0]["text"], "Overdue");
+    assert_eq!(rows[0]["line"], 3);
+}
+
+#[test]
+fn tasks_rejects_malformed_filters() {
+    obsidian_base()
+        .args(["tasks", "--filter", "task.due <", "--vault"])
+        .arg(fixture(""))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("^"));
+}
+
+#[test]
+fn projects_passes_conforming_vault() {
+    obsidian_vault()
+        .args(["projects", "--vault"])
+        .arg(fixture(""))
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn projects_reports_violations_as_jsonl() {
+    let vault = scratch_vault();
+    std::fs::write(
+        vault.path().join("Projects/Alpha/Alpha.md"),
+        "---\ntags:\n  - type/project\nproject.status: completed\nproject.priority: High\n---\n# Alpha\n\n- [ ] Leftover\n",
+    )
+    .unwrap();
+
+    let output = obsidian_vault()
+        .args(["projects", "--vault"])
+        .arg(vault.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["rule"], "updated");
+    assert_eq!(rows[1]["rule"], "open-task");
+    assert_eq!(rows[1]["file"], "Projects/Alpha/Alpha.md");
+    assert_eq!(rows[1]["line"], 9);
+}
+
+#[test]
+fn worklog_appends_to_project_and_daily_note() {
+    let vault = scratch_vault();
+    let project = vault.path().join("Projects/Alpha/Alpha.md");
+    obsidian_note()
+        .args(["worklog"])
+        .arg(&project)
+        .args(["Scoped the refactor", "--date", "2026-03-07", "--daily"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&project).unwrap();
+    assert!(content.starts_with("---\ntags:\n  - type/project\n"));
+    assert!(content.ends_with(
+        "Reading [[Clean Code]] for the refactor.\n\n## Work log\n\n### [[2026-03-07]]\n- Scoped the refactor\n"
+    ));
+    let daily = std::fs::read_to_string(vault.path().join("2026-03-07.md")).unwrap();
+    assert_eq!(daily, "- [[Alpha]]: Scoped the refactor\n");
+}
+
+#[test]
+fn worklog_takes_entries_starting_with_a_dash_after_double_dash() {
+    let vault = scratch_vault();
+    let project = vault.path().join("Projects/Alpha/Alpha.md");
+    obsidian_note()
+        .args(["worklog", "--date", "2026-03-07"])
+        .arg(&project)
+        .args(["--", "-5% latency"])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&project).unwrap();
+    assert!(content.ends_with("### [[2026-03-07]]\n- -5% latency\n"));
+}
+
+#[test]
+fn worklog_logs_help_flags_after_double_dash() {
+    let vault = scratch_vault();
+    let project = vault.path().join("Projects/Alpha/Alpha.md");
+    obsidian_note()
+        .args(["worklog", "--date", "2026-03-07", "--"])
+        .arg(&project)
+        .arg("-h")
+        .assert()
+        .success()
+        .stdout("");
+
+    let content = std::fs::read_to_string(&project).unwrap();
+    assert!(content.ends_with("### [[2026-03-07]]\n- -h\n"));
+}
+
+#[test]
+fn worklog_leaves_an_unreadable_daily_note_alone() {
+    let vault = scratch_vault();
+    let project = vault.path().join("Projects/Alpha/Alpha.md");
+    let daily = vault.path().join("2026-03-07.md");
+    std::fs::write(&daily, b"- kept \xff\n").unwrap();
+    obsidian_note()
+        .args(["worklog", "--date", "2026-03-07", "--daily"])
+        .arg(&project)
+        .arg("Scoped the refactor")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("2026-03-07.md: "));
+    assert_eq!(std::fs::read(&daily).unwrap(), b"- kept \xff\n");
+}
+
+#[test]
+fn query_falls_back_to_inline_fields_when_asked() {
+    let vault = scratch_vault();
+    std::fs::write(
+        vault.path().join("Review.md"),
+        "---\ntags: [review]\n---\n# Review\n\neffort:: 3\n",
+    )
+    .unwrap();
+
+    let query = |extra: &[&str]| {
+        let output = obsidian_base()
+            .args([
+                "query",
+                "--filter",
+                "note.effort == 3",
+                "--paths",
+                "--vault",
+            ])
+            .arg(vault.path())
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(query(&[]), "");
+    assert_eq!(query(&["--inline-fields"]).trim(), "Review.md");
+
+    let base = vault.path().join("Efforts.base");
+    std::fs::write(
+        &base,
+        "filters:\n  and:\n    - note.effort > 1\nviews:\n  - type: table\n    name: Efforts\n    order: [file.name, note.effort]\n",
+    )
+    .unwrap();
+    obsidian_base()
+        .arg(&base)
+        .args(["--inline-fields", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout("name,effort\nReview,3\n");
+}
+
+#[test]
+fn watch_picks_up_note_moved_into_new_folder() {
+    use std::io::BufRead;
+    use std::process::Stdio;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let vault = scratch_vault();
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("obsidian-base"))
+        .arg(vault.path().join("Library/Books/Reading.base"))
+        .args(["--view", "All", "--watch"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines() {
+            let row: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+            let _ = tx.send(row["file"].as_str().unwrap_or_default().to_owned());
+        }
+    });
+    let wait_for = |file: &str| {
+        while let Ok(seen) = rx.recv_timeout(Duration::from_secs(10)) {
+            if seen == file {
+                return true;
+            }
+        }
+        false
+    };
+
+    // The first resolution is emitted once the watcher is running
+    assert!(wait_for("Library/Books/Clean Code.md"));
+    std::fs::create_dir(vault.path().join("Sub")).unwrap();
+    std::fs::rename(
+        vault.path().join("Library/Books/Clean Code.md"),
+        vault.path().join("Sub/Clean Code.md"),
+    )
+    .unwrap();
+    let moved = wait_for("Sub/Clean Code.md");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(moved, "moved note never reappeared");
+}
+
+#[test]
+fn watch_rejects_snapshot_and_diff() {
+    for flag in ["--snapshot", "--diff"] {
+        obsidian_base()
+            .arg(fixture("Library/Books/Reading.base"))
+            .args(["--watch", flag, "snap.jsonl"])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::contains("cannot be combined with --watch"));
+    }
+}
+
+#[test]
+fn changes_requires_watch() {
+    obsidian_base()
+        .arg(fixture("Library/Books/Reading.base"))
+        .arg("--changes")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "--changes only applies to --watch",
+        ));
+}
+
+#[test]
+fn watch_emits_during_a_steady_stream_of_writes() {
+    use std::io::BufRead;
+    use std::process::Stdio;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+
+    let vault = scratch_vault();
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("obsidian-base"))
+        .arg(vault.path().join("Library/Books/Reading.base"))
+        .args(["--view", "All", "--watch", "--changes"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines() {
+            let _ = tx.send(line.unwrap());
+        }
+    });
+    // Give the watcher time to start before the stream begins
+    std::thread::sleep(Duration::from_millis(500));
+
+    // Rewrite a note faster than the debounce period until output appears
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer = {
+        let stop = Arc::clone(&stop);
+        let note = vault.path().join("Library/Books/Stream.md");
+        std::thread::spawn(move || {
+            for i in 0.. {
+                if stop.load(Ordering::Relaxed) || i > 300 {
+                    break;
+                }
+                let body = format!("---\ntags:\n  - type/item/book\n---\n# Stream {i}\n");
+                std::fs::write(&note, body).unwrap();
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        })
+    };
+    let line = rx.recv_timeout(Duration::from_secs(10));
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(
+        line.is_ok_and(|l| l.contains("Stream.md")),
+        "no update while writes kept coming"
+    );
+}
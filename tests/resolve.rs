@@ -0,0 +1,73 @@
+use forge_obsidian::base;
+use forge_obsidian::eval::ThisContext;
+use forge_obsidian::note;
+use std::path::{Path, PathBuf};
+
+fn fixture_vault() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/vault")
+        .canonicalize()
+        .unwrap()
+}
+
+fn resolve_fixture(base_rel: &str) -> base::ResolvedBase {
+    let vault = fixture_vault();
+    let base_path = vault.join(base_rel);
+    let spec = base::parse_file(&base_path).unwrap();
+    let notes = note::walk_vault(&vault);
+    let this_ctx = ThisContext::from_base_path(&vault, &base_path);
+    base::resolve(&spec, &notes, &this_ctx)
+}
+
+#[test]
+fn resolves_every_view() {
+    let resolved = resolve_fixture("Library/Books/Reading.base");
+    let names: Vec<&str> = resolved.views.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, vec!["All", "Favourites"]);
+}
+
+#[test]
+fn top_level_filter_and_sort_apply() {
+    let resolved = resolve_fixture("Library/Books/Reading.base");
+    let all = &resolved.views[0];
+    let files: Vec<&str> = all.rows.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(
+        files,
+        vec![
+            "Designing Data-Intensive Applications",
+            "The Pragmatic Programmer",
+            "Clean Code",
+        ]
+    );
+}
+
+#[test]
+fn view_filters_narrow_rows() {
+    let resolved = resolve_fixture("Library/Books/Reading.base");
+    let favourites = &resolved.views[1];
+    assert_eq!(favourites.rows.len(), 1);
+    assert_eq!(
+        favourites.rows[0].file,
+        "Library/Books/The Pragmatic Programmer.md"
+    );
+}
+
+#[test]
+fn rows_carry_typed_cells_per_column() {
+    let resolved = resolve_fixture("Library/Books/Reading.base");
+    let all = &resolved.views[0];
+    assert_eq!(all.columns, vec!["file.name", "rating", "finished"]);
+
+    let row = &all.rows[0];
+    assert_eq!(
+        row.cells[0],
+        serde_json::json!("Designing Data-Intensive Applications")
+    );
+    assert_eq!(row.cells[1], serde_json::json!(4));
+    assert_eq!(row.cells[2], serde_json::json!("2025-06-10"));
+
+    let obj = all.row_json(row);
+    assert_eq!(obj["view"], "All");
+    assert_eq!(obj["rating"], 4);
+    assert!(!obj.contains_key("file.name"));
+}